@@ -5,10 +5,23 @@ Copyright © 2024 - Elliot Simpson
 
 import argparse
 from pathlib import Path
+from typing import List
 
 from antiseptic._lowlevel import antiseptic
 
 
+def _rule_codes(value: str) -> List[str]:
+    """Splits a comma-separated list of rule codes given on the command line.
+
+    Args:
+        value: The rule codes (e.g. "AS001,AS002").
+
+    Returns:
+        The individual rule codes.
+    """
+    return [code.strip() for code in value.split(",") if code.strip()]
+
+
 def main() -> int:
     """The Python entry point for running the spell-check.
 
@@ -21,5 +34,77 @@ def main() -> int:
     parser.add_argument(
         "files", nargs="*", help="List of files or directories to check.", default=["."]
     )
+    parser.add_argument(
+        "--output-format",
+        choices=["concise", "json", "sarif", "github"],
+        help="Format in which to display diagnostics.",
+        default=None,
+    )
+    parser.add_argument(
+        "--column-mode",
+        choices=["char", "byte", "utf16"],
+        help="Units in which to count the columns of diagnostics.",
+        default=None,
+    )
+    fix_group = parser.add_mutually_exclusive_group()
+    fix_group.add_argument(
+        "--fix",
+        action="store_true",
+        help="Rewrite files with unambiguous corrections to spelling mistakes.",
+    )
+    fix_group.add_argument(
+        "--diff",
+        action="store_true",
+        help="Display the changes --fix would make as a diff, without writing them.",
+    )
+    fix_group.add_argument(
+        "--write-baseline",
+        metavar="PATH",
+        help="Record every current diagnostic in a baseline file, replacing it.",
+        default=None,
+    )
+    parser.add_argument(
+        "--baseline",
+        metavar="PATH",
+        help="Suppress diagnostics recorded in a baseline file, reporting stale entries.",
+        default=None,
+    )
+    parser.add_argument(
+        "--select",
+        type=_rule_codes,
+        help="Comma-separated rule codes to check, replacing those from the configuration.",
+        default=None,
+    )
+    parser.add_argument(
+        "--extend-select",
+        type=_rule_codes,
+        help="Comma-separated rule codes to check in addition to those selected.",
+        default=None,
+    )
+    parser.add_argument(
+        "--ignore",
+        type=_rule_codes,
+        help="Comma-separated rule codes not to check.",
+        default=None,
+    )
+    parser.add_argument(
+        "--explain",
+        metavar="CODE",
+        help="Display the documentation of a rule (e.g. AS001) and exit.",
+        default=None,
+    )
     args = parser.parse_args()
-    return antiseptic(args.files, str(Path(__file__).parent))
+    return antiseptic(
+        args.files,
+        str(Path(__file__).parent),
+        output_format=args.output_format,
+        column_mode=args.column_mode,
+        fix=args.fix,
+        diff=args.diff,
+        select=args.select,
+        extend_select=args.extend_select,
+        ignore=args.ignore,
+        explain=args.explain,
+        baseline=args.baseline,
+        write_baseline=args.write_baseline,
+    )
@@ -1,9 +1,32 @@
-def antiseptic(files: list[str], src: str) -> int:
+def antiseptic(
+    files: list[str],
+    src: str,
+    output_format: str | None = None,
+    column_mode: str | None = None,
+    fix: bool = False,
+    diff: bool = False,
+    select: list[str] | None = None,
+    extend_select: list[str] | None = None,
+    ignore: list[str] | None = None,
+    explain: str | None = None,
+    baseline: str | None = None,
+    write_baseline: str | None = None,
+) -> int:
     """Performs a spell-check over the provided files.
 
     Args:
         files: The list of globs to be processed by Antiseptic.
         src: The location of the Python code (and by extension, the Rust binary).
+        output_format: The format in which to display diagnostics (e.g. "json").
+        column_mode: The units in which to count columns (e.g. "utf16").
+        fix: Whether to rewrite files with unambiguous corrections.
+        diff: Whether to display the changes fixing would make, without writing them.
+        select: The rule codes to check, replacing those from the configuration.
+        extend_select: Further rule codes to check.
+        ignore: Further rule codes not to check.
+        explain: The code of a rule whose documentation is displayed instead.
+        baseline: The baseline file whose recorded diagnostics are suppressed.
+        write_baseline: The baseline file in which to record every diagnostic instead.
 
     Returns:
         The return code of the Rust binary.
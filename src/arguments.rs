@@ -0,0 +1,156 @@
+use std::env;
+use std::path::PathBuf;
+
+use colored::Colorize;
+use pyo3::types::PyList;
+use pyo3::types::PyString;
+
+use crate::diagnostic::ColumnMode;
+use crate::errors::all_errors::AntisepticError;
+use crate::output::emit::OutputFormat;
+use crate::rules::Rule;
+
+/// The options provided by the user on the command line.
+pub struct Arguments {
+    /// The format in which diagnostics are displayed.
+    pub output_format: OutputFormat,
+
+    /// The units in which the columns of diagnostics are counted.
+    pub column_mode: ColumnMode,
+
+    /// Whether or not files are rewritten with unambiguous corrections.
+    pub fix: bool,
+
+    /// Whether or not the changes that fixing would make are displayed, without being written.
+    pub diff: bool,
+
+    /// The rules which are checked, replacing `select` from the configuration.
+    pub select: Option<Vec<Rule>>,
+
+    /// Further rules which are checked, in addition to `extend-select` from the configuration.
+    pub extend_select: Vec<Rule>,
+
+    /// Further rules which are not checked, in addition to `ignore` from the configuration.
+    pub ignore: Vec<Rule>,
+
+    /// The baseline file whose recorded diagnostics are suppressed, if any.
+    pub baseline: Option<PathBuf>,
+
+    /// The baseline file in which every diagnostic is recorded, if any.
+    pub write_baseline: Option<PathBuf>,
+}
+
+/// Converts the rule codes given to a command-line option into rules. Each code may also be a
+/// selector matching several rules, i.e. `ALL` or a prefix such as `AS00`.
+///
+/// * `option` - The name of the option, as displayed in error messages.
+/// * `py_codes` - The rule codes provided by the Python interface, if the option was given.
+fn rules_from_python(
+    option: &str,
+    py_codes: Option<&PyList>,
+) -> Result<Option<Vec<Rule>>, AntisepticError> {
+    let py_codes = match py_codes {
+        Some(result) => result,
+        None => return Ok(None),
+    };
+    let mut rules = Vec::new();
+    for py_code in py_codes {
+        let code = match py_code.extract::<String>() {
+            Ok(result) => result,
+            Err(_e) => return Err(AntisepticError::StringParsingFailed),
+        };
+        let selected = Rule::from_selector(&code);
+        if selected.is_empty() {
+            eprintln!(
+                "{}",
+                format!(
+                    "Option \"{}\" contains unknown rule code \"{}\".",
+                    option, code
+                )
+                .red()
+            );
+            return Err(AntisepticError::UnknownRuleCode);
+        }
+        rules.extend(selected);
+    }
+    Ok(Some(rules))
+}
+
+/// Converts a path given to a command-line option into a `PathBuf`.
+///
+/// * `py_path` - The path provided by the Python interface, if the option was given.
+fn path_from_python(py_path: Option<&PyString>) -> Result<Option<PathBuf>, AntisepticError> {
+    match py_path {
+        Some(path) => match path.to_str() {
+            Ok(result) => Ok(Some(PathBuf::from(result))),
+            Err(_e) => Err(AntisepticError::StringParsingFailed),
+        },
+        None => Ok(None),
+    }
+}
+
+impl Arguments {
+    /// Converts the options provided by the Python interface into their Rust equivalents.
+    ///
+    /// * `py_output_format` - The name of the requested output format, if any.
+    /// * `py_column_mode` - The name of the requested column mode, if any.
+    /// * `fix` - Whether or not files are rewritten with unambiguous corrections.
+    /// * `diff` - Whether or not the changes that fixing would make are displayed.
+    /// * `py_select` - The rule codes given to `--select`, if any.
+    /// * `py_extend_select` - The rule codes given to `--extend-select`, if any.
+    /// * `py_ignore` - The rule codes given to `--ignore`, if any.
+    /// * `py_baseline` - The path given to `--baseline`, if any.
+    /// * `py_write_baseline` - The path given to `--write-baseline`, if any.
+    #[allow(clippy::too_many_arguments)]
+    pub fn from_python(
+        py_output_format: Option<&PyString>,
+        py_column_mode: Option<&PyString>,
+        fix: bool,
+        diff: bool,
+        py_select: Option<&PyList>,
+        py_extend_select: Option<&PyList>,
+        py_ignore: Option<&PyList>,
+        py_baseline: Option<&PyString>,
+        py_write_baseline: Option<&PyString>,
+    ) -> Result<Arguments, AntisepticError> {
+        let output_format = match py_output_format {
+            Some(name) => match name.to_str() {
+                Ok(result) => OutputFormat::from_name(result)?,
+                Err(_e) => return Err(AntisepticError::StringParsingFailed),
+            },
+            // When no format is requested, annotations are used if running inside GitHub Actions.
+            None if env::var("GITHUB_ACTIONS").is_ok_and(|value| value == "true") => {
+                OutputFormat::Github
+            }
+            None => OutputFormat::Concise,
+        };
+
+        let column_mode = match py_column_mode {
+            Some(name) => match name.to_str() {
+                Ok(result) => ColumnMode::from_name(result)?,
+                Err(_e) => return Err(AntisepticError::StringParsingFailed),
+            },
+            None => ColumnMode::Char,
+        };
+        if !output_format.supports_column_mode(column_mode) {
+            eprintln!(
+                "{}",
+                "The SARIF output format cannot count columns in bytes.".red()
+            );
+            return Err(AntisepticError::InvalidColumnMode);
+        }
+
+        Ok(Arguments {
+            output_format,
+            column_mode,
+            fix,
+            diff,
+            select: rules_from_python("--select", py_select)?,
+            extend_select: rules_from_python("--extend-select", py_extend_select)?
+                .unwrap_or_default(),
+            ignore: rules_from_python("--ignore", py_ignore)?.unwrap_or_default(),
+            baseline: path_from_python(py_baseline)?,
+            write_baseline: path_from_python(py_write_baseline)?,
+        })
+    }
+}
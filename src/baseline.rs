@@ -0,0 +1,268 @@
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+
+use colored::Colorize;
+use serde_json::json;
+use serde_json::Value;
+
+use crate::diagnostic::Diagnostic;
+use crate::errors::all_errors::AntisepticError;
+
+/// A diagnostic recorded in a baseline file. Rather than by its line number, the diagnostic is
+/// identified by a fingerprint of the line's contents, so that it still matches after lines are
+/// added or removed elsewhere in the file.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct BaselineEntry {
+    /// The file in which the diagnostic was found, relative to the working directory and using
+    /// forward slashes.
+    pub file: String,
+
+    /// The code of the rule which was violated (e.g. `AS001`).
+    pub rule: String,
+
+    /// The word which violated the rule.
+    pub word: String,
+
+    /// A hash of the line on which the diagnostic was found, ignoring surrounding whitespace.
+    pub fingerprint: String,
+}
+
+/// Returns the path of a file as recorded in a baseline, i.e. with forward slashes and without
+/// any leading `./`.
+///
+/// * `path` - The path to the file.
+fn baseline_path(path: &Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    path.strip_prefix("./").unwrap_or(&path).to_owned()
+}
+
+/// Returns the fingerprint of a line, ignoring indentation and trailing whitespace. This uses the
+/// 64-bit FNV-1a hash, which (unlike the hasher of the standard library) is stable between
+/// versions of Rust.
+///
+/// * `line` - The contents of the line.
+fn fingerprint(line: &str) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in line.trim().bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!("{:016x}", hash)
+}
+
+/// Converts every diagnostic into a baseline entry, reading each file again to fingerprint the
+/// line on which the diagnostic was found.
+///
+/// * `diagnostics` - Every problem found during the spell-check.
+fn entries_for(diagnostics: &[Diagnostic]) -> Result<Vec<BaselineEntry>, AntisepticError> {
+    let mut contents: HashMap<&PathBuf, String> = HashMap::new();
+    let mut entries = Vec::new();
+    for diagnostic in diagnostics {
+        let file = &diagnostic.position.file;
+        if !contents.contains_key(file) {
+            match fs::read_to_string(file) {
+                Ok(result) => contents.insert(file, result),
+                Err(_e) => {
+                    eprintln!(
+                        "{}{}{}",
+                        "File ".red(),
+                        file.to_string_lossy().red(),
+                        " could not be opened.".red()
+                    );
+                    return Err(AntisepticError::CheckedFileCouldNotBeOpened);
+                }
+            };
+        }
+        let line = contents[file]
+            .lines()
+            .nth(diagnostic.position.line_no as usize - 1)
+            .unwrap_or_default();
+        entries.push(BaselineEntry {
+            file: baseline_path(file),
+            rule: diagnostic.rule.code().to_owned(),
+            word: diagnostic.word.clone(),
+            fingerprint: fingerprint(line),
+        });
+    }
+    Ok(entries)
+}
+
+/// Records every diagnostic in a baseline file, replacing any existing baseline. Returns the
+/// number of entries written.
+///
+/// * `path` - The path to the baseline file.
+/// * `diagnostics` - Every problem found during the spell-check.
+pub fn write_baseline(path: &Path, diagnostics: &[Diagnostic]) -> Result<usize, AntisepticError> {
+    let mut entries = entries_for(diagnostics)?;
+    entries.sort();
+
+    let baseline = json!({
+        "version": 1,
+        "entries": entries
+            .iter()
+            .map(|entry| json!({
+                "file": entry.file,
+                "rule": entry.rule,
+                "word": entry.word,
+                "fingerprint": entry.fingerprint,
+            }))
+            .collect::<Vec<Value>>(),
+    });
+    let mut contents = serde_json::to_string_pretty(&baseline).unwrap();
+    contents.push('\n');
+    if fs::write(path, contents).is_err() {
+        eprintln!(
+            "{}{}{}",
+            "Baseline ".red(),
+            path.to_string_lossy().red(),
+            " could not be written.".red()
+        );
+        return Err(AntisepticError::BaselineCouldNotBeWritten);
+    }
+    Ok(entries.len())
+}
+
+/// Reads the entries of a baseline file written by `write_baseline`.
+///
+/// * `path` - The path to the baseline file.
+pub fn load_baseline(path: &Path) -> Result<Vec<BaselineEntry>, AntisepticError> {
+    let invalid = || {
+        eprintln!(
+            "{}{}{}",
+            "Baseline ".red(),
+            path.to_string_lossy().red(),
+            " could not be read.".red()
+        );
+        AntisepticError::InvalidBaseline
+    };
+
+    let contents = fs::read_to_string(path).map_err(|_e| invalid())?;
+    let baseline: Value = serde_json::from_str(&contents).map_err(|_e| invalid())?;
+    let mut entries = Vec::new();
+    for value in baseline["entries"].as_array().ok_or_else(invalid)? {
+        let field = |key: &str| value[key].as_str().map(str::to_owned).ok_or_else(invalid);
+        entries.push(BaselineEntry {
+            file: field("file")?,
+            rule: field("rule")?,
+            word: field("word")?,
+            fingerprint: field("fingerprint")?,
+        });
+    }
+    Ok(entries)
+}
+
+/// Removes every diagnostic which is recorded in the baseline. Each entry suppresses at most one
+/// diagnostic, so a word which is misspelled again on an identical line is still reported.
+///
+/// Returns the remaining diagnostics, along with the stale entries of the baseline which no longer
+/// match any diagnostic. Entries for files which were not checked are never stale.
+///
+/// * `baseline` - The entries of the baseline file.
+/// * `diagnostics` - Every problem found during the spell-check.
+/// * `checked_files` - Every file which was spell-checked.
+pub fn apply_baseline(
+    baseline: Vec<BaselineEntry>,
+    diagnostics: Vec<Diagnostic>,
+    checked_files: &BTreeSet<PathBuf>,
+) -> Result<(Vec<Diagnostic>, Vec<BaselineEntry>), AntisepticError> {
+    let mut unmatched: HashMap<BaselineEntry, usize> = HashMap::new();
+    for entry in baseline {
+        *unmatched.entry(entry).or_default() += 1;
+    }
+
+    let entries = entries_for(&diagnostics)?;
+    let mut remaining = Vec::new();
+    for (diagnostic, entry) in diagnostics.into_iter().zip(entries) {
+        match unmatched.get_mut(&entry) {
+            Some(count) if *count > 0 => *count -= 1,
+            _ => remaining.push(diagnostic),
+        }
+    }
+
+    let checked: BTreeSet<String> = checked_files
+        .iter()
+        .map(|file| baseline_path(file))
+        .collect();
+    let mut stale: Vec<BaselineEntry> = unmatched
+        .into_iter()
+        .filter(|(entry, _)| checked.contains(&entry.file))
+        .flat_map(|(entry, count)| vec![entry; count])
+        .collect();
+    stale.sort();
+    Ok((remaining, stale))
+}
+
+/// Reports the entries of the baseline which no longer match any diagnostic, and so can be
+/// removed by writing the baseline again.
+///
+/// * `stale` - The stale entries of the baseline.
+pub fn print_stale_entries(stale: &[BaselineEntry]) {
+    for entry in stale {
+        eprintln!(
+            "{}{}{} {} `{}`",
+            "WARNING: ".yellow(),
+            entry.file.bold(),
+            ":".cyan(),
+            entry.rule,
+            entry.word
+        );
+    }
+    if !stale.is_empty() {
+        eprintln!(
+            "{}",
+            format!(
+                "{} baseline entry(s) no longer match any diagnostic.",
+                stale.len()
+            )
+            .yellow()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::Rule;
+
+    /// Checks `apply_baseline` suppresses recorded diagnostics after their lines move, while
+    /// reporting new diagnostics and stale entries.
+    #[test]
+    fn apply_baseline_moved_lines() {
+        let file = std::env::temp_dir().join("antiseptic_apply_baseline_moved_lines.txt");
+        let diagnostic = |line_no, word: &str| {
+            Diagnostic::at(
+                file.to_str().unwrap(),
+                line_no,
+                1,
+                Rule::SpellingMistake,
+                word,
+            )
+        };
+
+        std::fs::write(&file, "helol there\nwrold\n").unwrap();
+        let baseline = entries_for(&[diagnostic(1, "helol"), diagnostic(2, "wrold")]).unwrap();
+
+        // A line is inserted above, and the second mistake is fixed.
+        std::fs::write(&file, "new lnie\n  helol there\nhelol there\n").unwrap();
+        let diagnostics = vec![
+            diagnostic(1, "lnie"),
+            diagnostic(2, "helol"),
+            diagnostic(3, "helol"),
+        ];
+        let checked_files = BTreeSet::from([file.clone()]);
+        let result = apply_baseline(baseline, diagnostics, &checked_files);
+        std::fs::remove_file(&file).unwrap();
+
+        let (remaining, stale) = result.unwrap();
+        let remaining: Vec<(u64, &str)> = remaining
+            .iter()
+            .map(|diagnostic| (diagnostic.position.line_no, diagnostic.word.as_str()))
+            .collect();
+        assert_eq!(remaining, vec![(1, "lnie"), (3, "helol")]);
+        assert_eq!(stale.len(), 1);
+        assert_eq!(stale[0].word, "wrold");
+    }
+}
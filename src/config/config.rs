@@ -1,15 +1,183 @@
 use std::borrow::BorrowMut;
+use std::path::PathBuf;
 
 use colored::Colorize;
+use regex::Regex;
 use toml::Table;
 
 use crate::errors::all_errors::AntisepticError;
+use crate::languages::language::Language;
+use crate::rules::Rule;
+use crate::suggest::damerau_levenshtein;
+
+/// Settings which apply only to files matching particular globs.
+#[derive(Default)]
+pub struct Override {
+    /// The globs indicating which files the override applies to.
+    pub files: Vec<String>,
+
+    /// Words which are permitted by the spell-checker, in addition to the global `allowed-words`.
+    pub allowed_words: Vec<String>,
+
+    /// The rules which are not checked.
+    pub ignore: Vec<Rule>,
+
+    /// The path to a dictionary used instead of the default dictionary, relative to the directory
+    /// containing the configuration file.
+    pub dictionary: Option<String>,
+}
+
+/// A word or phrase which is banned, even if it is spelled correctly.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ForbiddenWord {
+    /// The words of the phrase, in lowercase (e.g. `["master", "branch"]`).
+    pub words: Vec<String>,
+
+    /// The word or phrase which should be used instead.
+    pub replacement: Option<String>,
+
+    /// Further guidance displayed along with the diagnostic.
+    pub message: Option<String>,
+}
+
+/// How tokens containing digits (e.g. `utf8`, `x86` or `ipv6`) are handled.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DigitMode {
+    /// Digits separate words, so that e.g. `ipv6` is checked as `ipv`.
+    Split,
+
+    /// Digits are part of the word, so that e.g. `ipv6` needs to be an allowed word.
+    Check,
+
+    /// Tokens containing digits are not checked at all.
+    Skip,
+}
+
+/// The dialects of English whose spellings are distinguished, e.g. `colour` and `color`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Dialect {
+    /// British English (`en-GB`).
+    British,
+
+    /// American English (`en-US`).
+    American,
+
+    /// British English with Oxford spelling (`en-GB-oxendict`), which uses `-ize` rather than
+    /// `-ise`.
+    Oxford,
+}
+
+impl Dialect {
+    /// Returns the language tag of the dialect (e.g. `en-GB`).
+    pub fn tag(&self) -> &'static str {
+        match self {
+            Dialect::British => "en-GB",
+            Dialect::American => "en-US",
+            Dialect::Oxford => "en-GB-oxendict",
+        }
+    }
+}
+
+/// Which spellings are reported for belonging to a dialect.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DialectMode {
+    /// Spellings belonging to any dialect other than the given one are reported.
+    Only(Dialect),
+
+    /// Spellings belonging to a different dialect from the first such spelling in the same file
+    /// are reported.
+    Consistent,
+}
+
+/// Which kinds of text are skipped before a file is split into words.
+pub struct SkipSettings {
+    /// Whether URLs (e.g. `https://github.com/clockback/antiseptic`) are skipped.
+    pub urls: bool,
+
+    /// Whether email addresses (e.g. `someone@example.org`) are skipped.
+    pub emails: bool,
+
+    /// Whether Unix and Windows file paths (e.g. `/usr/local/bin` or `C:\Windows`) are skipped.
+    pub paths: bool,
+
+    /// Whether hostnames (e.g. `docs.python.org`) are skipped.
+    pub hostnames: bool,
+
+    /// Whether hexadecimal runs (e.g. git SHAs) and UUIDs are skipped.
+    pub hashes: bool,
+
+    /// Whether base64-encoded data (e.g. integrity hashes in lock files) and JSON Web Tokens are
+    /// skipped.
+    pub base64: bool,
+}
+
+impl Default for SkipSettings {
+    fn default() -> SkipSettings {
+        SkipSettings {
+            urls: true,
+            emails: true,
+            paths: true,
+            hostnames: true,
+            hashes: true,
+            base64: true,
+        }
+    }
+}
 
 /// The complete loaded contents from the TOML configuration file.
 pub struct Configuration {
     /// The list of globs needing to be excluded from Antiseptic's file search.
     pub exclude: Vec<String>,
     pub allowed_words: Vec<String>,
+
+    /// The maximum number of suggested corrections displayed for each spelling mistake.
+    pub max_suggestions: usize,
+
+    /// Settings which apply only to particular files, from both `[[overrides]]` and
+    /// `[per-file-ignores]`.
+    pub overrides: Vec<Override>,
+
+    /// Whether the names of the functions, classes and variables defined in Python files are
+    /// checked, in addition to their comments and strings.
+    pub python_check_identifiers: bool,
+
+    /// Whether fenced code blocks in Markdown files are checked, in the language named by their
+    /// info string.
+    pub markdown_check_code_blocks: bool,
+
+    /// The language of files matching each glob, taking precedence over their extension.
+    pub language_overrides: Vec<(String, Language)>,
+
+    /// Which kinds of text are skipped before a file is split into words.
+    pub skip: SkipSettings,
+
+    /// Patterns whose matches are skipped before a file is split into words.
+    pub ignore_regex: Vec<Regex>,
+
+    /// The number of characters a word needs in order to be checked.
+    pub min_word_length: usize,
+
+    /// How tokens containing digits are handled.
+    pub digits: DigitMode,
+
+    /// Words and phrases which are banned, even if they are spelled correctly.
+    pub forbidden_words: Vec<ForbiddenWord>,
+
+    /// Which spellings are reported for belonging to a dialect, if any.
+    pub dialect: Option<DialectMode>,
+
+    /// The rules which are checked, if not the default rules.
+    pub select: Option<Vec<Rule>>,
+
+    /// Further rules which are checked, in addition to those from `select`.
+    pub extend_select: Vec<Rule>,
+
+    /// The rules which are not checked in any file.
+    pub ignore: Vec<Rule>,
+
+    /// The directory containing the configuration file, against which paths in the configuration
+    /// are resolved.
+    pub config_dir: PathBuf,
 }
 
 impl Default for Configuration {
@@ -17,6 +185,23 @@ impl Default for Configuration {
         Configuration {
             exclude: Vec::new(),
             allowed_words: Vec::new(),
+            max_suggestions: 3,
+            overrides: Vec::new(),
+            python_check_identifiers: false,
+            markdown_check_code_blocks: false,
+            language_overrides: Vec::new(),
+            skip: SkipSettings {
+                ..Default::default()
+            },
+            ignore_regex: Vec::new(),
+            min_word_length: 4,
+            digits: DigitMode::Split,
+            forbidden_words: Vec::new(),
+            dialect: None,
+            select: None,
+            extend_select: Vec::new(),
+            ignore: Vec::new(),
+            config_dir: PathBuf::new(),
         }
     }
 }
@@ -29,11 +214,10 @@ fn get_exclude_array(
     config_toml: &Table,
     populate: &mut Vec<String>,
 ) -> Result<(), AntisepticError> {
-    let exclude_config_option = config_toml.get("exclude");
-    if exclude_config_option.is_some() {
-        let exclude_config_array_option = exclude_config_option.unwrap().as_array();
+    if let Some(exclude_config) = config_toml.get("exclude") {
+        let exclude_config_array_option = exclude_config.as_array();
         if exclude_config_array_option.is_none() {
-            println!(
+            eprintln!(
                 "{}",
                 "Configuration setting \"exclude\" should be array.".red()
             );
@@ -41,7 +225,7 @@ fn get_exclude_array(
         }
         for exclude_value in exclude_config_array_option.unwrap() {
             if !exclude_value.is_str() {
-                println!(
+                eprintln!(
                     "{}",
                     "Configuration setting \"exclude\" should contain only strings.".red()
                 );
@@ -62,11 +246,10 @@ fn get_allowed_words_array(
     config_toml: &Table,
     populate: &mut Vec<String>,
 ) -> Result<(), AntisepticError> {
-    let allowed_words_config_option = config_toml.get("allowed-words");
-    if allowed_words_config_option.is_some() {
-        let allowed_words_config_array_option = allowed_words_config_option.unwrap().as_array();
+    if let Some(allowed_words_config) = config_toml.get("allowed-words") {
+        let allowed_words_config_array_option = allowed_words_config.as_array();
         if allowed_words_config_array_option.is_none() {
-            println!(
+            eprintln!(
                 "{}",
                 "Configuration setting \"allowed-words\" should be array.".red()
             );
@@ -74,7 +257,7 @@ fn get_allowed_words_array(
         }
         for allowed_words_value in allowed_words_config_array_option.unwrap() {
             if !allowed_words_value.is_str() {
-                println!(
+                eprintln!(
                     "{}",
                     "Configuration setting \"allowed-words\" should only contain strings.".red()
                 );
@@ -87,6 +270,540 @@ fn get_allowed_words_array(
     Ok(())
 }
 
+/// Obtains the maximum number of suggested corrections displayed for each spelling mistake.
+///
+/// * `config_toml` - The TOML table containing Antiseptic's configuration.
+/// * `populate` - The maximum number of suggestions in memory.
+fn get_max_suggestions(config_toml: &Table, populate: &mut usize) -> Result<(), AntisepticError> {
+    if let Some(max_suggestions_config) = config_toml.get("max-suggestions") {
+        let max_suggestions_option = max_suggestions_config.as_integer();
+        if max_suggestions_option.is_none() || max_suggestions_option.unwrap() < 0 {
+            eprintln!(
+                "{}",
+                "Configuration setting \"max-suggestions\" should be a non-negative integer.".red()
+            );
+            return Err(AntisepticError::IncorrectConfigTOMLType);
+        }
+        *populate = max_suggestions_option.unwrap() as usize;
+    }
+
+    Ok(())
+}
+
+/// Obtains the number of characters a word needs in order to be checked.
+///
+/// * `config_toml` - The TOML table containing Antiseptic's configuration.
+/// * `populate` - The minimum word length in memory.
+fn get_min_word_length(config_toml: &Table, populate: &mut usize) -> Result<(), AntisepticError> {
+    if let Some(min_word_length_config) = config_toml.get("min-word-length") {
+        let min_word_length_option = min_word_length_config.as_integer();
+        if min_word_length_option.is_none() || min_word_length_option.unwrap() < 1 {
+            eprintln!(
+                "{}",
+                "Configuration setting \"min-word-length\" should be a positive integer.".red()
+            );
+            return Err(AntisepticError::IncorrectConfigTOMLType);
+        }
+        *populate = min_word_length_option.unwrap() as usize;
+    }
+
+    Ok(())
+}
+
+/// Obtains how tokens containing digits are handled.
+///
+/// * `config_toml` - The TOML table containing Antiseptic's configuration.
+/// * `populate` - The digit mode in memory.
+fn get_digits(config_toml: &Table, populate: &mut DigitMode) -> Result<(), AntisepticError> {
+    if let Some(digits_config) = config_toml.get("digits") {
+        *populate = match digits_config.as_str() {
+            Some("split") => DigitMode::Split,
+            Some("check") => DigitMode::Check,
+            Some("skip") => DigitMode::Skip,
+            _ => {
+                eprintln!(
+                    "{}",
+                    "Configuration setting \"digits\" should be \"split\", \"check\" or \"skip\"."
+                        .red()
+                );
+                return Err(AntisepticError::IncorrectConfigTOMLType);
+            }
+        };
+    }
+
+    Ok(())
+}
+
+/// Obtains an array of strings from a table, if present.
+///
+/// * `table` - The TOML table containing the setting.
+/// * `setting` - The name of the setting, as displayed in error messages.
+/// * `key` - The key of the setting within the table.
+/// * `populate` - The vector of strings to be populated in memory.
+fn get_string_array(
+    table: &Table,
+    setting: &str,
+    key: &str,
+    populate: &mut Vec<String>,
+) -> Result<(), AntisepticError> {
+    if let Some(value) = table.get(key) {
+        let array_option = value.as_array();
+        if array_option.is_none() {
+            eprintln!(
+                "{}",
+                format!("Configuration setting \"{}\" should be array.", setting).red()
+            );
+            return Err(AntisepticError::IncorrectConfigTOMLType);
+        }
+        for item in array_option.unwrap() {
+            if !item.is_str() {
+                eprintln!(
+                    "{}",
+                    format!(
+                        "Configuration setting \"{}\" should only contain strings.",
+                        setting
+                    )
+                    .red()
+                );
+                return Err(AntisepticError::IncorrectConfigTOMLType);
+            }
+            populate.push(item.as_str().unwrap().to_string());
+        }
+    }
+
+    Ok(())
+}
+
+/// Converts a list of rule codes into rules. Each code may also be a selector matching several
+/// rules, i.e. `ALL` or a prefix such as `AS00`.
+///
+/// * `setting` - The name of the setting, as displayed in error messages.
+/// * `codes` - The rule codes (e.g. `AS001`).
+/// * `populate` - The vector of rules to be populated in memory.
+fn codes_to_rules(
+    setting: &str,
+    codes: &[String],
+    populate: &mut Vec<Rule>,
+) -> Result<(), AntisepticError> {
+    for code in codes {
+        let rules = Rule::from_selector(code);
+        if rules.is_empty() {
+            eprintln!(
+                "{}",
+                format!(
+                    "Configuration setting \"{}\" contains unknown rule code \"{}\".",
+                    setting, code
+                )
+                .red()
+            );
+            return Err(AntisepticError::UnknownRuleCode);
+        }
+        populate.extend(rules);
+    }
+
+    Ok(())
+}
+
+/// Obtains the rules selected by one of the `select`, `extend-select` and `ignore` settings.
+///
+/// * `config_toml` - The TOML table containing Antiseptic's configuration.
+/// * `key` - The name of the setting.
+/// * `populate` - The vector of rules to be populated in memory.
+fn get_rule_selection(
+    config_toml: &Table,
+    key: &str,
+    populate: &mut Vec<Rule>,
+) -> Result<(), AntisepticError> {
+    let mut codes: Vec<String> = Vec::new();
+    get_string_array(config_toml, key, key, codes.borrow_mut())?;
+    codes_to_rules(key, &codes, populate)
+}
+
+/// Obtains every override from the `[[overrides]]` array of tables.
+///
+/// * `config_toml` - The TOML table containing Antiseptic's configuration.
+/// * `populate` - The vector of overrides to be populated in memory.
+fn get_overrides(config_toml: &Table, populate: &mut Vec<Override>) -> Result<(), AntisepticError> {
+    let overrides_config = match config_toml.get("overrides") {
+        Some(result) => result,
+        None => return Ok(()),
+    };
+    let overrides_array_option = overrides_config.as_array();
+    if overrides_array_option.is_none() {
+        eprintln!(
+            "{}",
+            "Configuration setting \"overrides\" should be an array of tables.".red()
+        );
+        return Err(AntisepticError::IncorrectConfigTOMLType);
+    }
+
+    for override_value in overrides_array_option.unwrap() {
+        let override_table = match override_value.as_table() {
+            Some(result) => result,
+            None => {
+                eprintln!(
+                    "{}",
+                    "Configuration setting \"overrides\" should be an array of tables.".red()
+                );
+                return Err(AntisepticError::IncorrectConfigTOMLType);
+            }
+        };
+
+        let mut override_settings = Override {
+            ..Default::default()
+        };
+        get_string_array(
+            override_table,
+            "overrides.files",
+            "files",
+            override_settings.files.borrow_mut(),
+        )?;
+        if override_settings.files.is_empty() {
+            eprintln!(
+                "{}",
+                "Each entry in \"overrides\" should have a non-empty \"files\" array.".red()
+            );
+            return Err(AntisepticError::IncorrectConfigTOMLType);
+        }
+        get_string_array(
+            override_table,
+            "overrides.allowed-words",
+            "allowed-words",
+            override_settings.allowed_words.borrow_mut(),
+        )?;
+        let mut ignore_codes: Vec<String> = Vec::new();
+        get_string_array(
+            override_table,
+            "overrides.ignore",
+            "ignore",
+            ignore_codes.borrow_mut(),
+        )?;
+        codes_to_rules(
+            "overrides.ignore",
+            &ignore_codes,
+            override_settings.ignore.borrow_mut(),
+        )?;
+        if let Some(dictionary_value) = override_table.get("dictionary") {
+            if !dictionary_value.is_str() {
+                eprintln!(
+                    "{}",
+                    "Configuration setting \"overrides.dictionary\" should be a string.".red()
+                );
+                return Err(AntisepticError::IncorrectConfigTOMLType);
+            }
+            override_settings.dictionary = Some(dictionary_value.as_str().unwrap().to_string());
+        }
+
+        populate.push(override_settings);
+    }
+
+    Ok(())
+}
+
+/// Obtains every override from the `[per-file-ignores]` table, which maps globs to the rules
+/// ignored in matching files.
+///
+/// * `config_toml` - The TOML table containing Antiseptic's configuration.
+/// * `populate` - The vector of overrides to be populated in memory.
+fn get_per_file_ignores(
+    config_toml: &Table,
+    populate: &mut Vec<Override>,
+) -> Result<(), AntisepticError> {
+    let per_file_ignores_config = match config_toml.get("per-file-ignores") {
+        Some(result) => result,
+        None => return Ok(()),
+    };
+    let per_file_ignores_table = match per_file_ignores_config.as_table() {
+        Some(result) => result,
+        None => {
+            eprintln!(
+                "{}",
+                "Configuration setting \"per-file-ignores\" should be a table.".red()
+            );
+            return Err(AntisepticError::IncorrectConfigTOMLType);
+        }
+    };
+
+    for glob in per_file_ignores_table.keys() {
+        let mut ignore_codes: Vec<String> = Vec::new();
+        get_string_array(
+            per_file_ignores_table,
+            "per-file-ignores",
+            glob,
+            ignore_codes.borrow_mut(),
+        )?;
+        let mut override_settings = Override {
+            files: vec![glob.to_owned()],
+            ..Default::default()
+        };
+        codes_to_rules(
+            "per-file-ignores",
+            &ignore_codes,
+            override_settings.ignore.borrow_mut(),
+        )?;
+        populate.push(override_settings);
+    }
+
+    Ok(())
+}
+
+/// Obtains a boolean setting from a table of related settings, e.g. `check-identifiers` in the
+/// `[python]` table.
+///
+/// * `config_toml` - The TOML table containing Antiseptic's configuration.
+/// * `table_name` - The name of the table, e.g. `python`.
+/// * `key` - The key of the setting within the table.
+/// * `populate` - The value of the setting in memory.
+fn get_table_setting(
+    config_toml: &Table,
+    table_name: &str,
+    key: &str,
+    populate: &mut bool,
+) -> Result<(), AntisepticError> {
+    let table_config = match config_toml.get(table_name) {
+        Some(result) => result,
+        None => return Ok(()),
+    };
+    let table = match table_config.as_table() {
+        Some(result) => result,
+        None => {
+            eprintln!(
+                "{}",
+                format!(
+                    "Configuration setting \"{}\" should be a table.",
+                    table_name
+                )
+                .red()
+            );
+            return Err(AntisepticError::IncorrectConfigTOMLType);
+        }
+    };
+    if let Some(setting_config) = table.get(key) {
+        match setting_config.as_bool() {
+            Some(result) => *populate = result,
+            None => {
+                eprintln!(
+                    "{}",
+                    format!(
+                        "Configuration setting \"{}.{}\" should be a boolean.",
+                        table_name, key
+                    )
+                    .red()
+                );
+                return Err(AntisepticError::IncorrectConfigTOMLType);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Obtains the language of files matching each glob from the `[language-overrides]` table. Files
+/// of a language without a frontend (e.g. `groovy`) are checked as plain text, with a warning if
+/// the name of the language is close to that of a frontend.
+///
+/// * `config_toml` - The TOML table containing Antiseptic's configuration.
+/// * `populate` - The vector of globs and languages to be populated in memory.
+fn get_language_overrides(
+    config_toml: &Table,
+    populate: &mut Vec<(String, Language)>,
+) -> Result<(), AntisepticError> {
+    let language_overrides_config = match config_toml.get("language-overrides") {
+        Some(result) => result,
+        None => return Ok(()),
+    };
+    let language_overrides_table = match language_overrides_config.as_table() {
+        Some(result) => result,
+        None => {
+            eprintln!(
+                "{}",
+                "Configuration setting \"language-overrides\" should be a table.".red()
+            );
+            return Err(AntisepticError::IncorrectConfigTOMLType);
+        }
+    };
+
+    for (glob, language_value) in language_overrides_table {
+        let language_name = match language_value.as_str() {
+            Some(result) => result,
+            None => {
+                eprintln!(
+                    "{}",
+                    "Configuration setting \"language-overrides\" should only contain strings."
+                        .red()
+                );
+                return Err(AntisepticError::IncorrectConfigTOMLType);
+            }
+        };
+        let language = match Language::from_name(language_name) {
+            Some(result) => result,
+            None => {
+                // A name one edit away from that of a frontend is likely a typo.
+                let characters: Vec<char> = language_name.to_lowercase().chars().collect();
+                let intended = ["python", "rust", "markdown", "text"]
+                    .into_iter()
+                    .find(|name| {
+                        let name: Vec<char> = name.chars().collect();
+                        damerau_levenshtein(&characters, &name) <= 1
+                    });
+                if let Some(intended) = intended {
+                    eprintln!(
+                        "{}",
+                        format!(
+                            "WARNING: Configuration setting \"language-overrides\" contains unknown \
+                             language \"{}\", did you mean \"{}\"? Matching files are checked as \
+                             plain text.",
+                            language_name, intended
+                        )
+                        .yellow()
+                    );
+                }
+                Language::PlainText
+            }
+        };
+        populate.push((glob.to_owned(), language));
+    }
+
+    Ok(())
+}
+
+/// Obtains every pattern whose matches should be skipped, checking that each is a valid regular
+/// expression.
+///
+/// * `config_toml` - The TOML table containing Antiseptic's configuration.
+/// * `populate` - The vector of compiled patterns to be populated in memory.
+fn get_ignore_regex(config_toml: &Table, populate: &mut Vec<Regex>) -> Result<(), AntisepticError> {
+    let mut patterns: Vec<String> = Vec::new();
+    get_string_array(
+        config_toml,
+        "ignore-regex",
+        "ignore-regex",
+        patterns.borrow_mut(),
+    )?;
+    for pattern in patterns {
+        match Regex::new(&pattern) {
+            Ok(result) => populate.push(result),
+            Err(error) => {
+                eprintln!(
+                    "{}",
+                    format!(
+                        "Configuration setting \"ignore-regex\" contains invalid pattern \"{}\":\n{}",
+                        pattern, error
+                    )
+                    .red()
+                );
+                return Err(AntisepticError::InvalidRegex);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Obtains which spellings are reported for belonging to a dialect.
+///
+/// * `config_toml` - The TOML table containing Antiseptic's configuration.
+/// * `populate` - The dialect mode in memory.
+fn get_dialect(
+    config_toml: &Table,
+    populate: &mut Option<DialectMode>,
+) -> Result<(), AntisepticError> {
+    if let Some(dialect_config) = config_toml.get("dialect") {
+        *populate = match dialect_config.as_str() {
+            Some("en-GB") => Some(DialectMode::Only(Dialect::British)),
+            Some("en-US") => Some(DialectMode::Only(Dialect::American)),
+            Some("en-GB-oxendict") => Some(DialectMode::Only(Dialect::Oxford)),
+            Some("consistent") => Some(DialectMode::Consistent),
+            _ => {
+                eprintln!(
+                    "{}",
+                    "Configuration setting \"dialect\" should be \"en-GB\", \"en-GB-oxendict\", \
+                     \"en-US\" or \"consistent\"."
+                        .red()
+                );
+                return Err(AntisepticError::IncorrectConfigTOMLType);
+            }
+        };
+    }
+
+    Ok(())
+}
+
+/// Obtains every word or phrase from the `[forbidden-words]` table. Each is mapped either to its
+/// replacement, or to a table with an optional `replacement` and `message`.
+///
+/// * `config_toml` - The TOML table containing Antiseptic's configuration.
+/// * `populate` - The vector of forbidden words to be populated in memory.
+fn get_forbidden_words(
+    config_toml: &Table,
+    populate: &mut Vec<ForbiddenWord>,
+) -> Result<(), AntisepticError> {
+    let forbidden_words_config = match config_toml.get("forbidden-words") {
+        Some(result) => result,
+        None => return Ok(()),
+    };
+    let forbidden_words_table = match forbidden_words_config.as_table() {
+        Some(result) => result,
+        None => {
+            eprintln!(
+                "{}",
+                "Configuration setting \"forbidden-words\" should be a table.".red()
+            );
+            return Err(AntisepticError::IncorrectConfigTOMLType);
+        }
+    };
+
+    for (phrase, forbidden_value) in forbidden_words_table {
+        let mut forbidden_word = ForbiddenWord {
+            words: phrase.split_whitespace().map(str::to_lowercase).collect(),
+            ..Default::default()
+        };
+        if forbidden_word.words.is_empty() {
+            eprintln!(
+                "{}",
+                "Configuration setting \"forbidden-words\" should not contain empty words.".red()
+            );
+            return Err(AntisepticError::IncorrectConfigTOMLType);
+        }
+
+        if let Some(replacement) = forbidden_value.as_str() {
+            forbidden_word.replacement = Some(replacement.to_owned());
+        } else if let Some(forbidden_table) = forbidden_value.as_table() {
+            for (key, value) in forbidden_table {
+                let text = match (key.as_str(), value.as_str()) {
+                    ("replacement" | "message", Some(result)) => result.to_owned(),
+                    _ => {
+                        eprintln!(
+                            "{}",
+                            format!(
+                                "Configuration setting \"forbidden-words\" has invalid entry \"{}\" for \"{}\". Only string values for \"replacement\" and \"message\" are permitted.",
+                                key, phrase
+                            )
+                            .red()
+                        );
+                        return Err(AntisepticError::IncorrectConfigTOMLType);
+                    }
+                };
+                if key == "replacement" {
+                    forbidden_word.replacement = Some(text);
+                } else {
+                    forbidden_word.message = Some(text);
+                }
+            }
+        } else {
+            eprintln!(
+                "{}",
+                "Configuration setting \"forbidden-words\" should only contain strings and tables."
+                    .red()
+            );
+            return Err(AntisepticError::IncorrectConfigTOMLType);
+        }
+        populate.push(forbidden_word);
+    }
+
+    Ok(())
+}
+
 /// Loads all the configuration TOML into a struct for later use.
 pub fn load_config(
     config_toml: &Table,
@@ -94,5 +811,100 @@ pub fn load_config(
 ) -> Result<(), AntisepticError> {
     get_exclude_array(config_toml, configuration.exclude.borrow_mut())?;
     get_allowed_words_array(config_toml, configuration.allowed_words.borrow_mut())?;
+    get_max_suggestions(config_toml, configuration.max_suggestions.borrow_mut())?;
+    get_min_word_length(config_toml, configuration.min_word_length.borrow_mut())?;
+    get_digits(config_toml, configuration.digits.borrow_mut())?;
+    get_overrides(config_toml, configuration.overrides.borrow_mut())?;
+    get_per_file_ignores(config_toml, configuration.overrides.borrow_mut())?;
+    get_table_setting(
+        config_toml,
+        "python",
+        "check-identifiers",
+        configuration.python_check_identifiers.borrow_mut(),
+    )?;
+    get_table_setting(
+        config_toml,
+        "markdown",
+        "check-code-blocks",
+        configuration.markdown_check_code_blocks.borrow_mut(),
+    )?;
+    get_language_overrides(config_toml, configuration.language_overrides.borrow_mut())?;
+    get_ignore_regex(config_toml, configuration.ignore_regex.borrow_mut())?;
+    get_forbidden_words(config_toml, configuration.forbidden_words.borrow_mut())?;
+    get_dialect(config_toml, configuration.dialect.borrow_mut())?;
+    if config_toml.contains_key("select") {
+        let mut select: Vec<Rule> = Vec::new();
+        get_rule_selection(config_toml, "select", select.borrow_mut())?;
+        configuration.select = Some(select);
+    }
+    get_rule_selection(
+        config_toml,
+        "extend-select",
+        configuration.extend_select.borrow_mut(),
+    )?;
+    get_rule_selection(config_toml, "ignore", configuration.ignore.borrow_mut())?;
+    get_table_setting(
+        config_toml,
+        "skip",
+        "urls",
+        configuration.skip.urls.borrow_mut(),
+    )?;
+    get_table_setting(
+        config_toml,
+        "skip",
+        "emails",
+        configuration.skip.emails.borrow_mut(),
+    )?;
+    get_table_setting(
+        config_toml,
+        "skip",
+        "paths",
+        configuration.skip.paths.borrow_mut(),
+    )?;
+    get_table_setting(
+        config_toml,
+        "skip",
+        "hostnames",
+        configuration.skip.hostnames.borrow_mut(),
+    )?;
+    get_table_setting(
+        config_toml,
+        "skip",
+        "hashes",
+        configuration.skip.hashes.borrow_mut(),
+    )?;
+    get_table_setting(
+        config_toml,
+        "skip",
+        "base64",
+        configuration.skip.base64.borrow_mut(),
+    )?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks `get_language_overrides` treats languages without a frontend as plain text.
+    #[test]
+    fn get_language_overrides_unknown_language() {
+        let mut language_overrides: Vec<(String, Language)> = Vec::new();
+        let config_toml =
+            "[language-overrides]\n\"*.pyw\" = \"py\"\n\"Jenkinsfile\" = \"groovy\"\n\"*.x\" = \"pyhton\"\n"
+                .parse::<Table>()
+                .unwrap();
+        assert_eq!(
+            get_language_overrides(&config_toml, &mut language_overrides),
+            Ok(())
+        );
+        assert_eq!(
+            language_overrides,
+            vec![
+                ("*.pyw".to_owned(), Language::Python),
+                ("*.x".to_owned(), Language::PlainText),
+                ("Jenkinsfile".to_owned(), Language::PlainText),
+            ]
+        );
+    }
+}
@@ -0,0 +1,209 @@
+use std::path::PathBuf;
+
+use colored::Colorize;
+
+use crate::errors::all_errors::AntisepticError;
+use crate::rules::Rule;
+
+/// The units in which the column of a diagnostic is counted.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ColumnMode {
+    /// Unicode scalar values, as displayed by most editors.
+    Char,
+
+    /// UTF-8 bytes, as used by tools which index into the raw file.
+    Byte,
+
+    /// UTF-16 code units, as used by the Language Server Protocol.
+    Utf16,
+}
+
+impl ColumnMode {
+    /// Obtains the column mode from its name as given on the command line.
+    ///
+    /// * `name` - The name of the column mode (e.g. `utf16`).
+    pub fn from_name(name: &str) -> Result<ColumnMode, AntisepticError> {
+        match name {
+            "char" => Ok(ColumnMode::Char),
+            "byte" => Ok(ColumnMode::Byte),
+            "utf16" => Ok(ColumnMode::Utf16),
+            _ => {
+                eprintln!("{}{}", "Unknown column mode: ".red(), name.red());
+                Err(AntisepticError::InvalidColumnMode)
+            }
+        }
+    }
+}
+
+/// The position of an identified token. This is primarily used in error output for the user to
+/// locate where an error has happened.
+#[derive(Clone, Debug)]
+pub struct ReadPosition {
+    /// The file which Antiseptic is checking.
+    pub file: PathBuf,
+
+    /// The line number of the file in which Antiseptic is checking. This follows 1-based indexing.
+    pub line_no: u64,
+
+    /// The index of the first character for the token or word where Antiseptic is checking. The
+    /// first character in a line is 1.
+    pub char_no: u64,
+
+    /// The same column as `char_no`, counted in UTF-8 bytes. The first byte in a line is 1.
+    pub byte_no: u64,
+
+    /// The same column as `char_no`, counted in UTF-16 code units. The first code unit in a line
+    /// is 1.
+    pub utf16_no: u64,
+}
+
+impl ReadPosition {
+    /// Returns the column of the position, counted in the requested units.
+    ///
+    /// * `column_mode` - The units in which the column is counted.
+    pub fn column(&self, column_mode: ColumnMode) -> u64 {
+        match column_mode {
+            ColumnMode::Char => self.char_no,
+            ColumnMode::Byte => self.byte_no,
+            ColumnMode::Utf16 => self.utf16_no,
+        }
+    }
+}
+
+/// A single problem found by Antiseptic, to be reported to the user.
+#[derive(Clone, Debug)]
+pub struct Diagnostic {
+    /// The position at which the problem was found.
+    pub position: ReadPosition,
+
+    /// The line on which the word ends, which only differs from that of `position` for phrases
+    /// spanning multiple lines.
+    pub end_line_no: u64,
+
+    /// The index following the last character of the word, on line `end_line_no`. The word
+    /// therefore spans from `position.char_no` up to, but excluding, `end_char_no`.
+    pub end_char_no: u64,
+
+    /// The same column as `end_char_no`, counted in UTF-8 bytes.
+    pub end_byte_no: u64,
+
+    /// The same column as `end_char_no`, counted in UTF-16 code units.
+    pub end_utf16_no: u64,
+
+    /// The rule which was violated.
+    pub rule: Rule,
+
+    /// The word which violated the rule.
+    pub word: String,
+
+    /// The token from which the word was taken (e.g. `leftRigth` for the word `Rigth`).
+    pub token: String,
+
+    /// Further guidance from the configuration, displayed after the message.
+    pub note: Option<String>,
+
+    /// Correctly spelled words that the user may have intended, closest first.
+    pub suggestions: Vec<String>,
+
+    /// The replacement for the word, if there is a single correction that can be safely applied.
+    pub fix: Option<String>,
+}
+
+impl Diagnostic {
+    /// Creates a diagnostic for a word on a single line, without a note, suggestions or a fix. The
+    /// end of the word is found from its length.
+    ///
+    /// * `position` - The position of the word.
+    /// * `rule` - The rule which was violated.
+    /// * `word` - The word which violated the rule.
+    /// * `token` - The token from which the word was taken.
+    pub fn new(position: ReadPosition, rule: Rule, word: &str, token: &str) -> Diagnostic {
+        Diagnostic {
+            end_line_no: position.line_no,
+            end_char_no: position.char_no + word.chars().count() as u64,
+            end_byte_no: position.byte_no + word.len() as u64,
+            end_utf16_no: position.utf16_no + word.encode_utf16().count() as u64,
+            position,
+            rule,
+            word: word.to_owned(),
+            token: token.to_owned(),
+            note: None,
+            suggestions: Vec::new(),
+            fix: None,
+        }
+    }
+
+    /// Returns the column following the last character of the word, counted in the requested
+    /// units.
+    ///
+    /// * `column_mode` - The units in which the column is counted.
+    pub fn end_column(&self, column_mode: ColumnMode) -> u64 {
+        match column_mode {
+            ColumnMode::Char => self.end_char_no,
+            ColumnMode::Byte => self.end_byte_no,
+            ColumnMode::Utf16 => self.end_utf16_no,
+        }
+    }
+
+    /// Returns the human-readable description of the problem.
+    pub fn message(&self) -> String {
+        let message = match self.rule {
+            Rule::SpellingMistake => format!("spelling mistake `{}`", self.word),
+            Rule::RepeatedWord => format!("repeated word `{}`", self.word),
+            Rule::ForbiddenWord => format!(
+                "forbidden word `{}`",
+                self.word
+                    .split_whitespace()
+                    .collect::<Vec<&str>>()
+                    .join(" ")
+            ),
+            Rule::DialectMismatch => format!("dialect mismatch `{}`", self.word),
+        };
+        let message = if self.suggestions.is_empty() {
+            message
+        } else {
+            format!("{}, did you mean {}?", message, self.suggestion_list())
+        };
+        match &self.note {
+            Some(note) => format!("{} {}", message, note),
+            None => message,
+        }
+    }
+
+    /// Returns the suggestions in prose, e.g. "`hello`, `hell` or `help`".
+    fn suggestion_list(&self) -> String {
+        let quoted: Vec<String> = self
+            .suggestions
+            .iter()
+            .map(|suggestion| format!("`{}`", suggestion))
+            .collect();
+        let (last, rest) = quoted.split_last().unwrap();
+        if rest.is_empty() {
+            last.to_owned()
+        } else {
+            format!("{} or {}", rest.join(", "), last)
+        }
+    }
+}
+
+#[cfg(test)]
+impl Diagnostic {
+    /// Creates a diagnostic for a word on a line containing only ASCII characters, where every
+    /// column mode counts the same column, taking the word as its own token.
+    ///
+    /// * `file` - The file in which the word was found.
+    /// * `line_no` - The line on which the word was found.
+    /// * `char_no` - The column at which the word begins.
+    /// * `rule` - The rule which was violated.
+    /// * `word` - The word which violated the rule.
+    pub fn at(file: &str, line_no: u64, char_no: u64, rule: Rule, word: &str) -> Diagnostic {
+        let position = ReadPosition {
+            file: PathBuf::from(file),
+            line_no,
+            char_no,
+            byte_no: char_no,
+            utf16_no: char_no,
+        };
+        Diagnostic::new(position, rule, word, word)
+    }
+}
@@ -0,0 +1,317 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufRead};
+use std::path::Path;
+use std::path::PathBuf;
+
+use colored::Colorize;
+
+use crate::config::config::Dialect;
+use crate::config::config::DialectMode;
+use crate::diagnostic::Diagnostic;
+use crate::diagnostic::ReadPosition;
+use crate::errors::all_errors::AntisepticError;
+use crate::fix::match_case;
+use crate::rules::Rule;
+
+/// A spelling which belongs to a dialect.
+pub struct Variant {
+    /// The dialect to which the spelling belongs, either British or American.
+    pub dialect: Dialect,
+
+    /// The spelling of the same word in the other dialect, in lowercase.
+    pub other_spelling: String,
+
+    /// Whether the spellings differ only by `-ise` and `-ize`, in which case both are British.
+    pub ize: bool,
+}
+
+impl Variant {
+    /// Returns whether or not the spelling is expected in a dialect.
+    ///
+    /// * `expected` - The expected dialect.
+    fn is_expected_in(&self, expected: Dialect) -> bool {
+        match expected {
+            Dialect::British => self.ize || self.dialect == Dialect::British,
+            Dialect::American => self.dialect == Dialect::American,
+            Dialect::Oxford if self.ize => self.dialect == Dialect::American,
+            Dialect::Oxford => self.dialect == Dialect::British,
+        }
+    }
+}
+
+/// The words which are spelled differently in British and American English.
+#[derive(Default)]
+pub struct DialectVariants {
+    /// The dialect of each spelling (in lowercase), along with the spelling of the same word in
+    /// the other dialect.
+    variants: HashMap<String, Variant>,
+}
+
+impl DialectVariants {
+    /// Loads the shipped tables of British and American spellings.
+    ///
+    /// * `src` - The path to the location of the Antiseptic code folder.
+    pub fn load(src: &Path) -> Result<DialectVariants, AntisepticError> {
+        let mut dialect_variants = DialectVariants::default();
+        dialect_variants.load_table(src, "en-variants.txt", false)?;
+        dialect_variants.load_table(src, "en-variants-ize.txt", true)?;
+        Ok(dialect_variants)
+    }
+
+    /// Loads a shipped table of British and American spellings.
+    ///
+    /// * `src` - The path to the location of the Antiseptic code folder.
+    /// * `file_name` - The name of the table within the dictionaries folder.
+    /// * `ize` - Whether the spellings in the table differ only by `-ise` and `-ize`.
+    fn load_table(
+        &mut self,
+        src: &Path,
+        file_name: &str,
+        ize: bool,
+    ) -> Result<(), AntisepticError> {
+        let mut path_buf = PathBuf::from(src);
+        path_buf.push("assets");
+        path_buf.push("dictionaries");
+        path_buf.push(file_name);
+
+        let open_table = match File::open(&path_buf) {
+            Ok(result) => result,
+            Err(_e) => {
+                eprintln!(
+                    "{}{}{}",
+                    "Error while reading dialect variants ".red(),
+                    path_buf.to_string_lossy().red(),
+                    ".".red()
+                );
+                return Err(AntisepticError::InvalidDictionaryPath);
+            }
+        };
+
+        for line in io::BufReader::new(open_table).lines().map_while(Result::ok) {
+            // Each line pairs the British spelling with the American spelling, e.g. `colour color`.
+            if line.starts_with('#') {
+                continue;
+            }
+            if let [british, american] = line.split_whitespace().collect::<Vec<&str>>()[..] {
+                self.insert(british, american, ize);
+            }
+        }
+        Ok(())
+    }
+
+    /// Records the British and American spellings of a word.
+    ///
+    /// * `british` - The British spelling.
+    /// * `american` - The American spelling.
+    /// * `ize` - Whether the spellings differ only by `-ise` and `-ize`.
+    fn insert(&mut self, british: &str, american: &str, ize: bool) {
+        self.variants.insert(
+            british.to_lowercase(),
+            Variant {
+                dialect: Dialect::British,
+                other_spelling: american.to_lowercase(),
+                ize,
+            },
+        );
+        self.variants.insert(
+            american.to_lowercase(),
+            Variant {
+                dialect: Dialect::American,
+                other_spelling: british.to_lowercase(),
+                ize,
+            },
+        );
+    }
+
+    /// Returns the dialect of a spelling, along with the spelling of the same word in the other
+    /// dialect, if the word is spelled differently between dialects.
+    ///
+    /// * `word` - The word, in any case.
+    pub fn lookup(&self, word: &str) -> Option<&Variant> {
+        self.variants.get(&word.to_lowercase())
+    }
+}
+
+/// Reports spellings belonging to an unexpected dialect within a single file.
+pub struct DialectChecker<'a> {
+    /// The words which are spelled differently in British and American English.
+    variants: &'a DialectVariants,
+
+    /// Which spellings are reported, if any.
+    mode: Option<DialectMode>,
+
+    /// The dialect of the first spelling found in the file which belongs to a dialect, along with
+    /// its line number. This is only used in the `consistent` mode.
+    file_dialect: Option<(Dialect, u64)>,
+
+    /// The dialect of the first `-ise` or `-ize` spelling found in the file, along with its line
+    /// number. This is tracked apart from `file_dialect`, since Oxford spelling combines `-ize`
+    /// with otherwise British spellings. This is only used in the `consistent` mode.
+    file_ize: Option<(Dialect, u64)>,
+}
+
+impl<'a> DialectChecker<'a> {
+    /// Creates a checker for a new file.
+    ///
+    /// * `variants` - The words which are spelled differently in British and American English.
+    /// * `mode` - Which spellings are reported, if any.
+    pub fn new(variants: &'a DialectVariants, mode: Option<DialectMode>) -> DialectChecker<'a> {
+        DialectChecker {
+            variants,
+            mode,
+            file_dialect: None,
+            file_ize: None,
+        }
+    }
+
+    /// Returns whether or not a word is spelled differently between dialects, in which case it is
+    /// checked by this rule rather than against the dictionary.
+    ///
+    /// Also includes recording a diagnostic in the event the word belongs to an unexpected dialect.
+    ///
+    /// * `read_position` - The position of the word.
+    /// * `word` - The word being checked.
+    /// * `token` - The token from which the word was taken.
+    /// * `diagnostics` - The list of diagnostics to be populated.
+    pub fn check(
+        &mut self,
+        read_position: &ReadPosition,
+        word: &str,
+        token: &str,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> bool {
+        let mode = match self.mode {
+            Some(result) => result,
+            None => return false,
+        };
+        let variant = match self.variants.lookup(word) {
+            Some(result) => result,
+            None => return false,
+        };
+
+        let note = match mode {
+            DialectMode::Only(expected) => {
+                if variant.is_expected_in(expected) {
+                    return true;
+                }
+                format!("Expected {} spelling.", expected.tag())
+            }
+            DialectMode::Consistent => {
+                let file_dialect = if variant.ize {
+                    &mut self.file_ize
+                } else {
+                    &mut self.file_dialect
+                };
+                let (expected, line_no) = match *file_dialect {
+                    Some(result) => result,
+                    None => {
+                        *file_dialect = Some((variant.dialect, read_position.line_no));
+                        return true;
+                    }
+                };
+                if variant.dialect == expected {
+                    return true;
+                }
+                let spelling = match (variant.ize, expected) {
+                    (true, Dialect::British) => "-ise",
+                    (true, _) => "-ize",
+                    (false, _) => expected.tag(),
+                };
+                format!("The file uses {} spelling from line {}.", spelling, line_no)
+            }
+        };
+
+        let replacement = match_case(word, &variant.other_spelling);
+        diagnostics.push(Diagnostic {
+            note: Some(note),
+            suggestions: vec![replacement.clone()],
+            fix: Some(replacement),
+            ..Diagnostic::new(read_position.clone(), Rule::DialectMismatch, word, token)
+        });
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns a table containing a few British and American spellings.
+    fn variants() -> DialectVariants {
+        let mut variants = DialectVariants::default();
+        variants.insert("colour", "color", false);
+        variants.insert("analyse", "analyze", false);
+        variants.insert("organise", "organize", true);
+        variants
+    }
+
+    /// Checks a series of words, returning the diagnostics recorded.
+    ///
+    /// * `mode` - Which spellings are reported.
+    /// * `words` - The words, one per line.
+    fn check_words(mode: DialectMode, words: &[&str]) -> Vec<Diagnostic> {
+        let variants = variants();
+        let mut checker = DialectChecker::new(&variants, Some(mode));
+        let mut diagnostics: Vec<Diagnostic> = Vec::new();
+        for (index, word) in words.iter().enumerate() {
+            let read_position = ReadPosition {
+                file: PathBuf::new(),
+                line_no: index as u64 + 1,
+                char_no: 1,
+                byte_no: 1,
+                utf16_no: 1,
+            };
+            checker.check(&read_position, word, word, &mut diagnostics);
+        }
+        diagnostics
+    }
+
+    /// Checks `DialectChecker::check` accepts both `-ise` and `-ize` spellings in `en-GB`, but only
+    /// `-ize` spellings in `en-GB-oxendict`.
+    #[test]
+    fn check_only() {
+        let words = [
+            "colour", "color", "analyse", "analyze", "organise", "Organize",
+        ];
+        let reported = |dialect| -> Vec<String> {
+            check_words(DialectMode::Only(dialect), &words)
+                .into_iter()
+                .map(|diagnostic| diagnostic.word)
+                .collect()
+        };
+        assert_eq!(reported(Dialect::British), vec!["color", "analyze"]);
+        assert_eq!(
+            reported(Dialect::Oxford),
+            vec!["color", "analyze", "organise"]
+        );
+        assert_eq!(
+            reported(Dialect::American),
+            vec!["colour", "analyse", "organise"]
+        );
+    }
+
+    /// Checks `DialectChecker::check` reports spellings which differ from the first in the file in
+    /// the `consistent` mode, tracking `-ise` and `-ize` spellings separately.
+    #[test]
+    fn check_consistent() {
+        let diagnostics = check_words(
+            DialectMode::Consistent,
+            &["Colour", "organize", "Organise", "color", "word"],
+        );
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].word, "Organise");
+        assert_eq!(diagnostics[0].fix, Some("Organize".to_owned()));
+        assert_eq!(
+            diagnostics[0].message(),
+            "dialect mismatch `Organise`, did you mean `Organize`? The file uses -ize spelling \
+             from line 2."
+        );
+        assert_eq!(diagnostics[1].word, "color");
+        assert_eq!(
+            diagnostics[1].message(),
+            "dialect mismatch `color`, did you mean `colour`? The file uses en-GB spelling from \
+             line 1."
+        );
+    }
+}
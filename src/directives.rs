@@ -0,0 +1,215 @@
+use crate::diagnostic::Diagnostic;
+
+/// The directives found in a single line of a file, which suppress diagnostics on that line.
+///
+/// The following directives are recognized within comments, in whatever syntax the language of the
+/// file uses. Since plain text has no comment syntax, they are recognized anywhere in it:
+///
+/// * `antiseptic: ignore` - Suppresses every diagnostic on the line.
+/// * `antiseptic: ignore[glubbage, glimp]` - Suppresses diagnostics for the listed words.
+/// * `noqa` - Suppresses every diagnostic on the line.
+/// * `noqa: AS001` - Suppresses diagnostics for the listed rule codes.
+/// * `antiseptic: skip-file` - Suppresses every diagnostic in the file.
+/// * `antiseptic: off` - Suppresses every diagnostic from this line until `antiseptic: on`.
+#[derive(Debug, Default, PartialEq)]
+pub struct LineDirectives {
+    /// Whether every diagnostic on the line is suppressed.
+    pub ignore_all: bool,
+
+    /// The rule codes whose diagnostics are suppressed on the line.
+    pub codes: Vec<String>,
+
+    /// The words (in lowercase) whose diagnostics are suppressed on the line.
+    pub words: Vec<String>,
+
+    /// Whether `noqa` appears on the line, in which case it is not itself a spelling mistake.
+    pub has_noqa: bool,
+
+    /// Whether every diagnostic in the file is suppressed.
+    pub skip_file: bool,
+
+    /// Whether checking is disabled from this line onwards.
+    pub disable: bool,
+
+    /// Whether checking is re-enabled after this line.
+    pub enable: bool,
+}
+
+/// Returns the text following a directive keyword, with any whitespace before it removed.
+///
+/// The keyword is only matched if it is not part of a longer word.
+///
+/// * `line` - The line being searched.
+/// * `keyword` - The keyword being searched for, in lowercase.
+fn text_after_keyword<'a>(line: &'a str, keyword: &str) -> Option<&'a str> {
+    let lowercase = line.to_ascii_lowercase();
+    let mut search_from = 0;
+    while let Some(found) = lowercase[search_from..].find(keyword) {
+        let start = search_from + found;
+        let end = start + keyword.len();
+        let preceded_by_word = lowercase[..start]
+            .chars()
+            .next_back()
+            .is_some_and(char::is_alphanumeric);
+        let followed_by_word = lowercase[end..]
+            .chars()
+            .next()
+            .is_some_and(char::is_alphanumeric);
+        if !preceded_by_word && !followed_by_word {
+            return Some(line[end..].trim_start());
+        }
+        search_from = end;
+    }
+    None
+}
+
+/// Returns whether or not a piece of text starts with a keyword that is not part of a longer word.
+///
+/// * `text` - The text being checked.
+/// * `keyword` - The keyword, e.g. `on`.
+fn starts_with_keyword(text: &str, keyword: &str) -> bool {
+    text.strip_prefix(keyword).is_some_and(|rest| {
+        !rest
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_alphanumeric() || c == '-')
+    })
+}
+
+/// Returns every rule code at the start of a piece of text, e.g. `AS001, AS002` in
+/// `AS001, AS002 some comment`.
+///
+/// * `text` - The text following `noqa:`.
+fn parse_codes(text: &str) -> Vec<String> {
+    let mut codes = Vec::new();
+    for piece in text.split(|c: char| c == ',' || c.is_whitespace()) {
+        if piece.is_empty() {
+            continue;
+        }
+        let letters = piece.chars().take_while(char::is_ascii_uppercase).count();
+        let is_code = letters > 0
+            && piece.len() > letters
+            && piece[letters..].chars().all(|c| c.is_ascii_digit());
+        if !is_code {
+            break;
+        }
+        codes.push(piece.to_owned());
+    }
+    codes
+}
+
+/// Finds every directive within a line.
+///
+/// * `line` - The line of the file.
+pub fn parse_line(line: &str) -> LineDirectives {
+    let mut directives = LineDirectives {
+        ..Default::default()
+    };
+
+    if let Some(text) = text_after_keyword(line, "antiseptic:") {
+        if starts_with_keyword(text, "skip-file") {
+            directives.skip_file = true;
+        } else if starts_with_keyword(text, "off") {
+            directives.disable = true;
+        } else if starts_with_keyword(text, "on") {
+            directives.enable = true;
+        } else if let Some(after_ignore) = text.strip_prefix("ignore") {
+            // The word-scoped form lists the words to be ignored in square brackets.
+            match after_ignore
+                .strip_prefix('[')
+                .and_then(|rest| rest.split_once(']'))
+            {
+                Some((words, _)) => directives.words.extend(
+                    words
+                        .split(',')
+                        .map(|word| word.trim().to_lowercase())
+                        .filter(|word| !word.is_empty()),
+                ),
+                None if starts_with_keyword(text, "ignore") => directives.ignore_all = true,
+                None => (),
+            }
+        }
+    }
+
+    if let Some(text) = text_after_keyword(line, "noqa") {
+        directives.has_noqa = true;
+        match text.strip_prefix(':') {
+            Some(codes) => directives.codes = parse_codes(codes.trim_start()),
+            None => directives.ignore_all = true,
+        }
+    }
+
+    directives
+}
+
+impl LineDirectives {
+    /// Returns whether or not a diagnostic on the line is suppressed.
+    ///
+    /// * `diagnostic` - A diagnostic found on the line.
+    pub fn suppresses(&self, diagnostic: &Diagnostic) -> bool {
+        let word = diagnostic.word.to_lowercase();
+        self.ignore_all
+            || (self.has_noqa && word == "noqa")
+            || self.codes.iter().any(|code| code == diagnostic.rule.code())
+            || self.words.contains(&word)
+            || self.words.contains(&diagnostic.token.to_lowercase())
+    }
+
+    /// Removes any diagnostics on the line that are suppressed.
+    ///
+    /// * `diagnostics` - The list of diagnostics, ending with those found on the line.
+    /// * `line_start` - The number of diagnostics that were found before the line.
+    pub fn apply(&self, diagnostics: &mut Vec<Diagnostic>, line_start: usize) {
+        let line_diagnostics = diagnostics.split_off(line_start);
+        diagnostics.extend(
+            line_diagnostics
+                .into_iter()
+                .filter(|diagnostic| !self.suppresses(diagnostic)),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks `parse_line` recognizes a line-level ignore in any comment syntax.
+    #[test]
+    fn parse_line_ignore() {
+        assert!(parse_line("x = 'helol'  # antiseptic: ignore").ignore_all);
+        assert!(parse_line("let x = \"helol\"; // Antiseptic: ignore").ignore_all);
+        assert!(!parse_line("the antiseptic is ignored").ignore_all);
+    }
+
+    /// Checks `parse_line` recognizes the word-scoped form of ignore.
+    #[test]
+    fn parse_line_ignore_words() {
+        let directives = parse_line("glubbage glimp  # antiseptic: ignore[glubbage, Glimp]");
+        assert!(!directives.ignore_all);
+        assert_eq!(directives.words, vec!["glubbage", "glimp"]);
+    }
+
+    /// Checks `parse_line` recognizes noqa, with and without rule codes.
+    #[test]
+    fn parse_line_noqa() {
+        assert!(parse_line("import helol  # noqa").ignore_all);
+        let directives = parse_line("import helol  # noqa: AS001, E501 unused import");
+        assert!(!directives.ignore_all);
+        assert_eq!(directives.codes, vec!["AS001", "E501"]);
+        assert!(!parse_line("the canoqa").has_noqa);
+    }
+
+    /// Checks `parse_line` recognizes skip-file.
+    #[test]
+    fn parse_line_skip_file() {
+        assert!(parse_line("# antiseptic: skip-file").skip_file);
+    }
+
+    /// Checks `parse_line` recognizes off and on, but not words merely starting with them.
+    #[test]
+    fn parse_line_off_on() {
+        assert!(parse_line("<!-- antiseptic: off -->").disable);
+        assert!(parse_line("# antiseptic: on").enable);
+        assert!(!parse_line("# antiseptic: only").enable);
+    }
+}
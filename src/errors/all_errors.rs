@@ -17,4 +17,12 @@ pub enum AntisepticError {
     MissingConfig,
     ReadingDictionaryFailed,
     IssueReadingFile,
+    InvalidOutputFormat,
+    CheckedFileCouldNotBeWritten,
+    UnknownRuleCode,
+    InvalidGlob,
+    InvalidRegex,
+    InvalidColumnMode,
+    InvalidBaseline,
+    BaselineCouldNotBeWritten,
 }
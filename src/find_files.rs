@@ -39,7 +39,7 @@ fn consider_collecting_file(
         let exclude_str = exclude_value.as_str();
         let glob = Glob::new(exclude_str);
         if glob.is_err() {
-            println!("{}{}", "Invalid glob: ".red(), exclude_str.red())
+            eprintln!("{}{}", "Invalid glob: ".red(), exclude_str.red())
         }
 
         // Checks whether the file should be excluded.
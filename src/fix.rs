@@ -0,0 +1,303 @@
+use std::collections::BTreeMap;
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+
+use colored::Colorize;
+use similar::TextDiff;
+
+use crate::diagnostic::ColumnMode;
+use crate::diagnostic::Diagnostic;
+use crate::errors::all_errors::AntisepticError;
+use crate::rules::Rule;
+
+/// The outcome of applying every available fix, before anything is written to disk.
+pub struct FixResult {
+    /// The diagnostics which were fixed.
+    pub fixed: Vec<Diagnostic>,
+
+    /// The diagnostics which could not be fixed.
+    pub remaining: Vec<Diagnostic>,
+
+    /// Every changed file, along with its contents before and after fixing.
+    pub changes: Vec<(PathBuf, String, String)>,
+}
+
+/// Returns the replacement for a word, following the casing pattern of the original word.
+///
+/// Lowercase (e.g. `helol`), capitalized (e.g. `Helol`) and all-caps (e.g. `HELOL`) words are
+/// recognized. Since `process_token` splits camelCase tokens into words following one of these
+/// patterns, each piece of a camelCase token keeps its casing.
+///
+/// * `original` - The word being replaced.
+/// * `replacement` - The replacement word, in any case.
+pub fn match_case(original: &str, replacement: &str) -> String {
+    let mut characters = original.chars();
+    let first_is_uppercase = characters.next().is_some_and(char::is_uppercase);
+    let rest: Vec<char> = characters.collect();
+
+    if first_is_uppercase && !rest.is_empty() && rest.iter().all(|c| !c.is_lowercase()) {
+        return replacement.to_uppercase();
+    }
+    let lowercase = replacement.to_lowercase();
+    if first_is_uppercase {
+        let mut lowercase_characters = lowercase.chars();
+        return match lowercase_characters.next() {
+            Some(first) => first.to_uppercase().chain(lowercase_characters).collect(),
+            None => lowercase,
+        };
+    }
+    lowercase
+}
+
+/// Returns the byte offset within a file's contents of a character at a given position.
+///
+/// * `contents` - The contents of the file.
+/// * `line_no` - The line number of the character, following 1-based indexing.
+/// * `char_no` - The index of the character within the line, following 1-based indexing.
+fn find_offset(contents: &str, line_no: u64, char_no: u64) -> Option<usize> {
+    let line_start = if line_no <= 1 {
+        0
+    } else {
+        contents.match_indices('\n').nth((line_no - 2) as usize)?.0 + 1
+    };
+    contents[line_start..]
+        .char_indices()
+        .nth(char_no.checked_sub(1)? as usize)
+        .map(|(offset, _)| line_start + offset)
+}
+
+/// Applies fixes to a file's contents, returning the new contents along with whether or not each
+/// diagnostic was fixed.
+///
+/// A fix is only applied if the word is found exactly where the diagnostic indicates.
+///
+/// * `contents` - The contents of the file.
+/// * `diagnostics` - The diagnostics found in the file.
+fn fix_contents(contents: &str, diagnostics: &[&Diagnostic]) -> (String, Vec<bool>) {
+    let mut edits: Vec<(usize, usize, &str, usize)> = Vec::new();
+    for (index, diagnostic) in diagnostics.iter().enumerate() {
+        let replacement = match &diagnostic.fix {
+            Some(result) => result,
+            None => continue,
+        };
+        let position = &diagnostic.position;
+        let offset = match find_offset(contents, position.line_no, position.char_no) {
+            Some(result) => result,
+            None => continue,
+        };
+        if !contents[offset..].starts_with(&diagnostic.word) {
+            continue;
+        }
+
+        // A repeated word is removed along with the whitespace separating it from the first
+        // occurrence.
+        let end = offset + diagnostic.word.len();
+        let start = match diagnostic.rule {
+            Rule::RepeatedWord => contents[..offset].trim_end().len(),
+            _ => offset,
+        };
+        edits.push((start, end - start, replacement, index));
+    }
+
+    // Applies the edits from the end of the file backwards, so that earlier offsets stay valid.
+    // Any edit overlapping one that has already been applied is skipped.
+    let mut fixed_contents = contents.to_owned();
+    let mut fixed = vec![false; diagnostics.len()];
+    let mut applied_from = contents.len();
+    edits.sort();
+    for (offset, length, replacement, index) in edits.into_iter().rev() {
+        if offset + length > applied_from {
+            continue;
+        }
+        fixed_contents.replace_range(offset..offset + length, replacement);
+        fixed[index] = true;
+        applied_from = offset;
+    }
+    (fixed_contents, fixed)
+}
+
+/// Determines the fixed contents of every file with a fixable diagnostic, without writing
+/// anything to disk.
+///
+/// * `diagnostics` - Every problem found during the spell-check.
+/// * `invalid_files` - Files that failed UTF-8 decoding, which are never fixed.
+pub fn fix_all(
+    diagnostics: Vec<Diagnostic>,
+    invalid_files: &HashSet<PathBuf>,
+) -> Result<FixResult, AntisepticError> {
+    let mut result = FixResult {
+        fixed: Vec::new(),
+        remaining: Vec::new(),
+        changes: Vec::new(),
+    };
+
+    // Groups the diagnostics by file, so each file is only read once.
+    let mut by_file: BTreeMap<PathBuf, Vec<usize>> = BTreeMap::new();
+    for (index, diagnostic) in diagnostics.iter().enumerate() {
+        if diagnostic.fix.is_some() && !invalid_files.contains(&diagnostic.position.file) {
+            by_file
+                .entry(diagnostic.position.file.clone())
+                .or_default()
+                .push(index);
+        }
+    }
+
+    let mut is_fixed = vec![false; diagnostics.len()];
+    for (file, indices) in by_file {
+        let contents = match fs::read_to_string(&file) {
+            Ok(result) => result,
+            Err(_e) => {
+                eprintln!(
+                    "{}{}{}",
+                    "File ".red(),
+                    file.to_string_lossy().red(),
+                    " could not be opened.".red()
+                );
+                return Err(AntisepticError::CheckedFileCouldNotBeOpened);
+            }
+        };
+        let file_diagnostics: Vec<&Diagnostic> =
+            indices.iter().map(|index| &diagnostics[*index]).collect();
+        let (fixed_contents, fixed) = fix_contents(&contents, &file_diagnostics);
+        for (index, was_fixed) in indices.into_iter().zip(fixed) {
+            is_fixed[index] = was_fixed;
+        }
+        if fixed_contents != contents {
+            result.changes.push((file, contents, fixed_contents));
+        }
+    }
+
+    // Separates the fixed diagnostics from the remaining diagnostics, keeping their order.
+    for (diagnostic, was_fixed) in diagnostics.into_iter().zip(is_fixed) {
+        if was_fixed {
+            result.fixed.push(diagnostic);
+        } else {
+            result.remaining.push(diagnostic);
+        }
+    }
+
+    Ok(result)
+}
+
+/// Writes the fixed contents of every changed file to disk.
+///
+/// * `changes` - Every changed file, along with its contents before and after fixing.
+pub fn write_changes(changes: &[(PathBuf, String, String)]) -> Result<(), AntisepticError> {
+    for (file, _, fixed_contents) in changes {
+        if fs::write(file, fixed_contents).is_err() {
+            eprintln!(
+                "{}{}{}",
+                "File ".red(),
+                file.to_string_lossy().red(),
+                " could not be written.".red()
+            );
+            return Err(AntisepticError::CheckedFileCouldNotBeWritten);
+        }
+    }
+    Ok(())
+}
+
+/// Returns a unified diff of every changed file.
+///
+/// * `changes` - Every changed file, along with its contents before and after fixing.
+pub fn unified_diff(changes: &[(PathBuf, String, String)]) -> String {
+    let mut diff = String::new();
+    for (file, contents, fixed_contents) in changes {
+        let path = file.to_string_lossy();
+        diff.push_str(
+            &TextDiff::from_lines(contents, fixed_contents)
+                .unified_diff()
+                .header(&path, &path)
+                .to_string(),
+        );
+    }
+    diff
+}
+
+/// Reports every fix that was applied.
+///
+/// * `fixed` - The diagnostics which were fixed.
+/// * `column_mode` - The units in which columns are counted.
+pub fn print_fixes(fixed: &[Diagnostic], column_mode: ColumnMode) {
+    for diagnostic in fixed {
+        let position = &diagnostic.position;
+        let (action, change) = match diagnostic.fix.as_deref().unwrap_or_default() {
+            "" => ("Removed", format!("`{}`", diagnostic.word)),
+            replacement => (
+                "Fixed",
+                format!("`{}` -> `{}`", diagnostic.word, replacement),
+            ),
+        };
+        eprintln!(
+            "{}{}{}{}{}{} {} {}",
+            position.file.to_string_lossy().bold(),
+            ":".cyan(),
+            position.line_no,
+            ":".cyan(),
+            position.column(column_mode),
+            ":".cyan(),
+            action.green().bold(),
+            change
+        );
+    }
+    if !fixed.is_empty() {
+        eprintln!("Fixed {} problem(s).", fixed.len());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks `unified_diff` shows only the changed lines, with the file name in the header.
+    #[test]
+    fn unified_diff_changed_lines() {
+        let changes = vec![(
+            PathBuf::from("myfile.txt"),
+            "hello\nwrold\n".to_owned(),
+            "hello\nworld\n".to_owned(),
+        )];
+        assert_eq!(
+            unified_diff(&changes),
+            "--- myfile.txt\n+++ myfile.txt\n@@ -1,2 +1,2 @@\n hello\n-wrold\n+world\n"
+        );
+    }
+
+    /// Checks `match_case` follows lowercase, capitalized and all-caps patterns.
+    #[test]
+    fn match_case_patterns() {
+        assert_eq!(match_case("wrold", "world"), "world");
+        assert_eq!(match_case("Wrold", "world"), "World");
+        assert_eq!(match_case("WROLD", "world"), "WORLD");
+    }
+
+    /// Checks `fix_contents` replaces a word within a camelCase token on a later line.
+    #[test]
+    fn fix_contents_within_token() {
+        let diagnostic = Diagnostic {
+            token: "helloWrold".to_owned(),
+            fix: Some("World".to_owned()),
+            ..Diagnostic::at("myfile.txt", 2, 8, Rule::SpellingMistake, "Wrold")
+        };
+        let (contents, fixed) = fix_contents("é\n  helloWrold!\n", &[&diagnostic]);
+        assert_eq!(contents, "é\n  helloWorld!\n");
+        assert_eq!(fixed, vec![true]);
+    }
+
+    /// Checks `fix_contents` removes a repeated word along with the whitespace before it, even
+    /// across a line break.
+    #[test]
+    fn fix_contents_repeated_word() {
+        let repeated = |line_no, char_no| Diagnostic {
+            fix: Some(String::new()),
+            ..Diagnostic::at("myfile.txt", line_no, char_no, Rule::RepeatedWord, "the")
+        };
+        let (contents, fixed) = fix_contents(
+            "Fetch the the cat from the\n  the mat.\n",
+            &[&repeated(1, 11), &repeated(2, 3)],
+        );
+        assert_eq!(contents, "Fetch the cat from the mat.\n");
+        assert_eq!(fixed, vec![true, true]);
+    }
+}
@@ -0,0 +1,103 @@
+use std::ops::Range;
+
+use crate::config::config::Configuration;
+use crate::languages::markdown;
+use crate::languages::python;
+use crate::languages::rust;
+
+/// Every language whose files Antiseptic understands.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Language {
+    /// Every word in the file is checked.
+    PlainText,
+
+    /// Only comments, docstrings and string literals are checked, along with the names of the
+    /// functions, classes and variables the file defines if `python.check-identifiers` is set.
+    Python,
+
+    /// Only comments (including doc comments) and string literals are checked.
+    Rust,
+
+    /// Prose and headings are checked, but code, link destinations and HTML tags are not. Fenced
+    /// code blocks are checked in their own language if `markdown.check-code-blocks` is set.
+    Markdown,
+}
+
+impl Language {
+    /// Obtains a language from its name, e.g. the info string of a fenced code block.
+    ///
+    /// * `name` - The name of the language, in any case (e.g. `python` or `py`).
+    pub fn from_name(name: &str) -> Option<Language> {
+        match name.to_lowercase().as_str() {
+            "text" | "txt" | "plaintext" => Some(Language::PlainText),
+            "python" | "py" | "pyi" => Some(Language::Python),
+            "rust" | "rs" => Some(Language::Rust),
+            "markdown" | "md" => Some(Language::Markdown),
+            _ => None,
+        }
+    }
+
+    /// Finds the ranges of characters in a file which are spell-checked.
+    ///
+    /// * `characters` - Every character in the file.
+    /// * `configuration` - The settings loaded from the configuration file.
+    /// * `comments` - The list of comment ranges to be populated. Plain text has no comments.
+    pub fn checked_ranges(
+        &self,
+        characters: &[char],
+        configuration: &Configuration,
+        comments: &mut Vec<Range<usize>>,
+    ) -> Vec<Range<usize>> {
+        match self {
+            Language::PlainText => std::iter::once(0..characters.len()).collect(),
+            Language::Python => {
+                python::checked_ranges(characters, configuration.python_check_identifiers, comments)
+            }
+            Language::Rust => rust::checked_ranges(characters, comments),
+            Language::Markdown => markdown::checked_ranges(characters, configuration, comments),
+        }
+    }
+
+    /// Returns the contents of a file with every character that is not spell-checked replaced by
+    /// a space, along with the contents with every character outside of a comment replaced by a
+    /// space. Newlines are always kept, so that every character keeps its position.
+    ///
+    /// Directives are only recognized within the latter. Since plain text has no comment syntax,
+    /// it is returned unchanged for both.
+    ///
+    /// * `contents` - The contents of the file.
+    /// * `configuration` - The settings loaded from the configuration file.
+    pub fn checked_text(&self, contents: &str, configuration: &Configuration) -> (String, String) {
+        if *self == Language::PlainText {
+            return (contents.to_owned(), contents.to_owned());
+        }
+        let characters: Vec<char> = contents.chars().collect();
+        let mut comments = Vec::new();
+        let checked_ranges = self.checked_ranges(&characters, configuration, &mut comments);
+        (
+            mask(&characters, &checked_ranges),
+            mask(&characters, &comments),
+        )
+    }
+}
+
+/// Replaces every character outside of the checked ranges with a space, except for newlines.
+///
+/// * `characters` - Every character in the file.
+/// * `ranges` - The ranges of characters which are spell-checked.
+pub fn mask(characters: &[char], ranges: &[Range<usize>]) -> String {
+    let mut is_checked = vec![false; characters.len()];
+    for range in ranges {
+        for checked in &mut is_checked[range.clone()] {
+            *checked = true;
+        }
+    }
+    characters
+        .iter()
+        .zip(is_checked)
+        .map(|(character, checked)| match (character, checked) {
+            (_, true) | ('\n', _) => *character,
+            _ => ' ',
+        })
+        .collect()
+}
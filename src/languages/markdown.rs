@@ -0,0 +1,556 @@
+use std::ops::Range;
+
+use crate::config::config::Configuration;
+use crate::languages::language::Language;
+
+/// Returns the index of the newline ending the line containing a given index, or the length of
+/// the file if it is the last line.
+///
+/// * `characters` - Every character in the file.
+/// * `start` - An index within the line.
+fn line_end(characters: &[char], start: usize) -> usize {
+    let mut end = start;
+    while end < characters.len() && characters[end] != '\n' {
+        end += 1;
+    }
+    end
+}
+
+/// Returns whether or not the characters at a given index spell out a piece of text.
+///
+/// * `characters` - Every character in the file.
+/// * `index` - The index at which the text may begin.
+/// * `text` - The text being searched for.
+fn starts_with(characters: &[char], index: usize, text: &str) -> bool {
+    text.chars()
+        .enumerate()
+        .all(|(offset, expected)| characters.get(index + offset) == Some(&expected))
+}
+
+/// Returns the number of consecutive occurrences of a character starting at a given index.
+///
+/// * `characters` - Every character in the file.
+/// * `start` - The index of the first occurrence.
+/// * `character` - The character being counted.
+fn run_length(characters: &[char], start: usize, character: char) -> usize {
+    characters[start..]
+        .iter()
+        .take_while(|current| **current == character)
+        .count()
+}
+
+/// Finds the fence (e.g. ```` ``` ```` or `~~~`) at the start of a line, if any, returning the
+/// fence character, the length of the fence and the index following it.
+///
+/// * `characters` - Every character in the file.
+/// * `line_start` - The index of the first character of the line.
+fn fence(characters: &[char], line_start: usize) -> Option<(char, usize, usize)> {
+    let mut index = line_start;
+    while index < line_start + 3 && characters.get(index) == Some(&' ') {
+        index += 1;
+    }
+    let character = *characters.get(index)?;
+    if character != '`' && character != '~' {
+        return None;
+    }
+    let length = run_length(characters, index, character);
+    if length < 3 {
+        return None;
+    }
+    Some((character, length, index + length))
+}
+
+/// Returns whether or not a line is a link reference definition, e.g. `[label]: https://...`.
+///
+/// * `characters` - Every character in the file.
+/// * `line_start` - The index of the first character of the line.
+fn is_reference_definition(characters: &[char], line_start: usize) -> bool {
+    let end = line_end(characters, line_start);
+    let line: String = characters[line_start..end].iter().collect();
+    let trimmed = line.trim_start_matches(' ');
+    if line.len() - trimmed.len() > 3 || !trimmed.starts_with('[') || trimmed.starts_with("[^") {
+        return false;
+    }
+    trimmed
+        .find("]:")
+        .is_some_and(|close| !trimmed[1..close].contains(']'))
+}
+
+/// Returns whether or not a line contains only whitespace.
+///
+/// * `characters` - Every character in the file.
+/// * `line_start` - The index of the first character of the line.
+fn is_blank(characters: &[char], line_start: usize) -> bool {
+    characters[line_start..line_end(characters, line_start)]
+        .iter()
+        .all(|character| character.is_whitespace())
+}
+
+/// Returns whether or not a line is indented by at least four columns, as is the content of an
+/// indented code block.
+///
+/// * `characters` - Every character in the file.
+/// * `line_start` - The index of the first character of the line.
+fn is_indented(characters: &[char], line_start: usize) -> bool {
+    let spaces = run_length(characters, line_start, ' ');
+    spaces >= 4 || characters.get(line_start + spaces) == Some(&'\t')
+}
+
+/// Returns whether or not a line begins a list item, e.g. `- item` or `1. item`.
+///
+/// * `characters` - Every character in the file.
+/// * `line_start` - The index of the first character of the line.
+fn is_list_item(characters: &[char], line_start: usize) -> bool {
+    let mut index = line_start + run_length(characters, line_start, ' ').min(3);
+    match characters.get(index) {
+        Some('-' | '*' | '+') => index += 1,
+        Some(character) if character.is_ascii_digit() => {
+            while characters.get(index).is_some_and(char::is_ascii_digit) {
+                index += 1;
+            }
+            if !matches!(characters.get(index), Some('.' | ')')) {
+                return false;
+            }
+            index += 1;
+        }
+        _ => return false,
+    }
+    characters
+        .get(index)
+        .is_none_or(|character| character.is_whitespace())
+}
+
+/// Returns the index following an HTML tag or autolink which begins at a given index, if any. The
+/// tag must lie within a single line and be made up of a tag name and attributes, so that a `<`
+/// used as a less-than sign does not hide the prose following it.
+///
+/// * `characters` - Every character in the file.
+/// * `start` - The index of the `<`.
+/// * `end` - The index at which the search stops.
+fn tag_end(characters: &[char], start: usize, end: usize) -> Option<usize> {
+    let end = end.min(line_end(characters, start));
+    let is_end = |index: usize, character: char| index < end && characters[index] == character;
+
+    // An autolink, e.g. `<https://x.io>` or `<me@x.io>`, is a URI or email address without spaces.
+    let close = (start + 1..end).find(|close| characters[*close] == '>')?;
+    let contents = &characters[start + 1..close];
+    if contents.iter().any(|c| *c == ':' || *c == '@')
+        && !contents.iter().any(|c| c.is_whitespace() || *c == '<')
+    {
+        return Some(close + 1);
+    }
+
+    let mut index = start + 1;
+    if is_end(index, '/') {
+        index += 1;
+    }
+    if !(index < end && characters[index].is_ascii_alphabetic()) {
+        return None;
+    }
+    while index < end && (characters[index].is_ascii_alphanumeric() || characters[index] == '-') {
+        index += 1;
+    }
+
+    loop {
+        let whitespace_start = index;
+        while index < end && characters[index].is_whitespace() {
+            index += 1;
+        }
+        if is_end(index, '>') {
+            return Some(index + 1);
+        }
+        if is_end(index, '/') && is_end(index + 1, '>') {
+            return Some(index + 2);
+        }
+
+        // Attributes are separated from the tag name and from each other by whitespace.
+        let is_name_start = |c: char| c.is_ascii_alphabetic() || c == '_' || c == ':';
+        if index == whitespace_start || !(index < end && is_name_start(characters[index])) {
+            return None;
+        }
+        let is_name = |c: char| c.is_ascii_alphanumeric() || "_.:-".contains(c);
+        while index < end && is_name(characters[index]) {
+            index += 1;
+        }
+
+        let name_end = index;
+        while index < end && characters[index].is_whitespace() {
+            index += 1;
+        }
+        if !is_end(index, '=') {
+            index = name_end;
+            continue;
+        }
+        index += 1;
+        while index < end && characters[index].is_whitespace() {
+            index += 1;
+        }
+        match characters.get(index) {
+            Some(quote @ ('"' | '\'')) if index < end => {
+                index = (index + 1..end).find(|close| characters[*close] == *quote)? + 1;
+            }
+            _ => {
+                let value_start = index;
+                while index < end
+                    && !characters[index].is_whitespace()
+                    && !"\"'=<>`".contains(characters[index])
+                {
+                    index += 1;
+                }
+                if index == value_start {
+                    return None;
+                }
+            }
+        }
+    }
+}
+
+/// Returns the index of the character closing a construct, skipping nested pairs of the opening
+/// and closing characters.
+///
+/// * `characters` - Every character in the file.
+/// * `open_index` - The index of the opening character.
+/// * `end` - The index at which the search stops.
+/// * `open` - The opening character, e.g. `(`.
+/// * `close` - The closing character, e.g. `)`.
+fn matching_close(
+    characters: &[char],
+    open_index: usize,
+    end: usize,
+    open: char,
+    close: char,
+) -> Option<usize> {
+    let mut depth = 0;
+    for (index, character) in characters.iter().enumerate().take(end).skip(open_index) {
+        if *character == open {
+            depth += 1;
+        } else if *character == close {
+            depth -= 1;
+            if depth == 0 {
+                return Some(index);
+            }
+        }
+    }
+    None
+}
+
+/// Finds the checked ranges of a region of prose, skipping code spans, link destinations, HTML
+/// tags, autolinks and bare URLs. The contents of HTML comments are checked as prose, but are also
+/// recorded as comments.
+///
+/// * `characters` - Every character in the file.
+/// * `region` - The range of characters containing the prose.
+/// * `ranges` - The list of checked ranges to be populated.
+/// * `comments` - The list of comment ranges to be populated.
+fn inline_ranges(
+    characters: &[char],
+    region: Range<usize>,
+    ranges: &mut Vec<Range<usize>>,
+    comments: &mut Vec<Range<usize>>,
+) {
+    let end = region.end;
+    let mut index = region.start;
+    let mut segment_start = index;
+    while index < end {
+        let character = characters[index];
+        let next = characters.get(index + 1).copied();
+        let previous = index.checked_sub(1).map(|previous| characters[previous]);
+
+        // HTML comments are the only comments in Markdown, and so the only place for directives.
+        if starts_with(characters, index, "<!--") {
+            let close = (index + 4..end)
+                .find(|close| starts_with(characters, *close, "-->"))
+                .unwrap_or(end);
+            comments.push(index + 4..close);
+        }
+
+        // Finds the end of any construct which is not checked, starting at this character.
+        let skipped_end = match character {
+            '\\' => Some((index + 2).min(end)),
+            '`' => {
+                // A code span ends with a run of backticks of the same length as its opening.
+                let length = run_length(characters, index, '`');
+                let mut close = index + length;
+                let mut found = None;
+                while close < end {
+                    if characters[close] == '`' {
+                        let close_length = run_length(characters, close, '`');
+                        if close_length == length {
+                            found = Some(close + length);
+                            break;
+                        }
+                        close += close_length;
+                    } else {
+                        close += 1;
+                    }
+                }
+                if found.is_none() {
+                    // Unmatched backticks are literal text.
+                    index += length;
+                    continue;
+                }
+                found
+            }
+            '<' if next.is_some_and(|next| next.is_alphabetic() || next == '/') => {
+                tag_end(characters, index, end)
+            }
+            ']' if next == Some('(') => {
+                matching_close(characters, index + 1, end, '(', ')').map(|close| close + 1)
+            }
+            ']' if next == Some('[') => (index + 1..end)
+                .find(|close| characters[*close] == ']')
+                .map(|close| close + 1),
+            'h' | 'w'
+                if !previous.is_some_and(char::is_alphanumeric)
+                    && (starts_with(characters, index, "http://")
+                        || starts_with(characters, index, "https://")
+                        || starts_with(characters, index, "www.")) =>
+            {
+                (index..end)
+                    .find(|close| {
+                        characters[*close].is_whitespace()
+                            || characters[*close] == '<'
+                            || characters[*close] == '>'
+                    })
+                    .or(Some(end))
+            }
+            _ => None,
+        };
+
+        match skipped_end {
+            Some(skipped_end) => {
+                // The closing bracket of a link's text is kept, so that only the destination is
+                // skipped.
+                let skipped_start = if character == ']' { index + 1 } else { index };
+                ranges.push(segment_start..skipped_start);
+                index = skipped_end;
+                segment_start = index;
+            }
+            None => index += 1,
+        }
+    }
+    ranges.push(segment_start..end);
+}
+
+/// Finds the ranges of a Markdown file which are spell-checked: prose and headings. Code spans,
+/// link destinations, HTML tags, URLs, link reference definitions and indented code blocks are
+/// skipped, as are fenced code blocks, unless `markdown.check-code-blocks` is set and the language
+/// of the block is known.
+///
+/// * `characters` - Every character in the file.
+/// * `configuration` - The settings loaded from the configuration file.
+/// * `comments` - The list of comment ranges to be populated, including those of checked code
+///   blocks.
+pub fn checked_ranges(
+    characters: &[char],
+    configuration: &Configuration,
+    comments: &mut Vec<Range<usize>>,
+) -> Vec<Range<usize>> {
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+    let mut prose_start = 0;
+    let mut line_start = 0;
+    // An indented code block cannot interrupt a paragraph, and indented lines within a list are
+    // the continuation of a list item rather than code.
+    let mut in_paragraph = false;
+    let mut in_list = false;
+    while line_start < characters.len() {
+        let end = line_end(characters, line_start);
+
+        if is_blank(characters, line_start) {
+            in_paragraph = false;
+            line_start = end + 1;
+            continue;
+        }
+
+        if is_indented(characters, line_start) {
+            if in_paragraph || in_list {
+                line_start = end + 1;
+                continue;
+            }
+            inline_ranges(characters, prose_start..line_start, &mut ranges, comments);
+
+            // The code block continues over blank lines up to the last indented line.
+            let mut block_end = end + 1;
+            let mut next_start = end + 1;
+            while next_start < characters.len()
+                && (is_indented(characters, next_start) || is_blank(characters, next_start))
+            {
+                let next_end = line_end(characters, next_start);
+                if !is_blank(characters, next_start) {
+                    block_end = next_end + 1;
+                }
+                next_start = next_end + 1;
+            }
+            line_start = block_end;
+            prose_start = block_end;
+            continue;
+        }
+
+        if is_list_item(characters, line_start) {
+            in_list = true;
+        } else if !in_paragraph {
+            in_list = false;
+        }
+
+        if is_reference_definition(characters, line_start) {
+            inline_ranges(characters, prose_start..line_start, &mut ranges, comments);
+            line_start = end + 1;
+            prose_start = line_start;
+            continue;
+        }
+
+        let (fence_character, fence_length, info_start) = match fence(characters, line_start) {
+            Some(result) => result,
+            None => {
+                // Headings take up a single line, so are never followed by a paragraph's text.
+                in_paragraph = characters[line_start..end]
+                    .iter()
+                    .find(|character| **character != ' ')
+                    .is_none_or(|character| *character != '#');
+                line_start = end + 1;
+                continue;
+            }
+        };
+        inline_ranges(characters, prose_start..line_start, &mut ranges, comments);
+
+        // The code block ends with a fence at least as long as the one it began with.
+        let info: String = characters[info_start..end].iter().collect();
+        let content_start = (end + 1).min(characters.len());
+        let mut content_end = content_start;
+        let mut block_end = characters.len();
+        while content_end < characters.len() {
+            let closing_end = line_end(characters, content_end);
+            if let Some((character, length, after)) = fence(characters, content_end) {
+                let rest: String = characters[after..closing_end].iter().collect();
+                if character == fence_character && length >= fence_length && rest.trim().is_empty()
+                {
+                    block_end = closing_end + 1;
+                    break;
+                }
+            }
+            content_end = closing_end + 1;
+        }
+        content_end = content_end.min(characters.len());
+
+        // Checks the contents of the block in the language named by its info string.
+        let language_name = info
+            .split(|c: char| c.is_whitespace() || c == ',' || c == '{' || c == '}')
+            .map(|word| word.trim_start_matches('.'))
+            .find(|word| !word.is_empty());
+        if configuration.markdown_check_code_blocks {
+            if let Some(language) = language_name.and_then(Language::from_name) {
+                let mut block_comments = Vec::new();
+                let block_ranges = language.checked_ranges(
+                    &characters[content_start..content_end],
+                    configuration,
+                    &mut block_comments,
+                );
+                let offset =
+                    |range: Range<usize>| range.start + content_start..range.end + content_start;
+                ranges.extend(block_ranges.into_iter().map(offset));
+                comments.extend(block_comments.into_iter().map(offset));
+            }
+        }
+
+        line_start = block_end;
+        prose_start = block_end;
+    }
+    inline_ranges(
+        characters,
+        prose_start.min(characters.len())..characters.len(),
+        &mut ranges,
+        comments,
+    );
+
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the text of every checked range in a piece of Markdown.
+    ///
+    /// * `source` - The Markdown source.
+    /// * `check_code_blocks` - Whether or not fenced code blocks are checked.
+    fn checked_text(source: &str, check_code_blocks: bool) -> Vec<String> {
+        let characters: Vec<char> = source.chars().collect();
+        let configuration = Configuration {
+            markdown_check_code_blocks: check_code_blocks,
+            ..Default::default()
+        };
+        checked_ranges(&characters, &configuration, &mut Vec::new())
+            .into_iter()
+            .map(|range| characters[range].iter().collect::<String>())
+            .filter(|text| !text.trim().is_empty())
+            .collect()
+    }
+
+    /// Checks `checked_ranges` skips code spans, link destinations, HTML tags and URLs.
+    #[test]
+    fn checked_ranges_inline() {
+        let source =
+            "# Title\nUse `fooBar` [the docs](https://x.io/a_b) <br/> or https://y.io now.\n";
+        assert_eq!(
+            checked_text(source, false),
+            vec!["# Title\nUse ", " [the docs]", " or ", " now.\n"]
+        );
+    }
+
+    /// Checks `checked_ranges` skips fenced code blocks and link reference definitions.
+    #[test]
+    fn checked_ranges_fenced_blocks() {
+        let source = "Before\n```python\nimport np  # note\n```\n[docs]: https://x.io\nAfter\n";
+        assert_eq!(checked_text(source, false), vec!["Before\n", "After\n"]);
+    }
+
+    /// Checks `checked_ranges` checks fenced code blocks in their own language when requested.
+    #[test]
+    fn checked_ranges_fenced_blocks_language() {
+        let source = "~~~~ python\nimport np  # note\n~~~~\n```unknown\nxyzzy\n```\n";
+        assert_eq!(checked_text(source, true), vec![" note"]);
+    }
+
+    /// Checks `checked_ranges` only skips text between `<` and `>` which has the shape of an HTML
+    /// tag or autolink on a single line.
+    #[test]
+    fn checked_ranges_tags() {
+        let source = "If a <b then 2 > c, see <a href=\"x.io\" title='b c'>\nthis <x@y.io> 2 < 3\n";
+        assert_eq!(
+            checked_text(source, false),
+            vec!["If a <b then 2 > c, see ", "\nthis ", " 2 < 3\n"]
+        );
+        let source = "Less <than\nmore> text\n";
+        assert_eq!(
+            checked_text(source, false),
+            vec!["Less <than\nmore> text\n"]
+        );
+    }
+
+    /// Checks `checked_ranges` skips indented code blocks, but not indented lines within a
+    /// paragraph or a list item.
+    #[test]
+    fn checked_ranges_indented_blocks() {
+        let source = "Before\n\n    let fooBar = 1;\n\n\tbazQux()\nAfter\n";
+        assert_eq!(checked_text(source, false), vec!["Before\n\n", "After\n"]);
+        let source = "Para\n    graph\n\n- item\n\n    continued\n";
+        assert_eq!(
+            checked_text(source, false),
+            vec!["Para\n    graph\n\n- item\n\n    continued\n"]
+        );
+    }
+
+    /// Checks `checked_ranges` records the contents of HTML comments as comments.
+    #[test]
+    fn checked_ranges_html_comments() {
+        let source = "Glubbage <!-- antiseptic: ignore -->\n`<!-- code -->`\n";
+        let characters: Vec<char> = source.chars().collect();
+        let mut comments = Vec::new();
+        checked_ranges(&characters, &Configuration::default(), &mut comments);
+        let comments: Vec<String> = comments
+            .into_iter()
+            .map(|range| characters[range].iter().collect())
+            .collect();
+        assert_eq!(comments, vec![" antiseptic: ignore "]);
+    }
+}
@@ -0,0 +1,5 @@
+pub mod language;
+pub mod markdown;
+pub mod python;
+pub mod registry;
+pub mod rust;
@@ -0,0 +1,291 @@
+use std::ops::Range;
+
+/// The Python keywords, which are never considered to be identifiers being defined.
+const KEYWORDS: [&str; 35] = [
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import",
+    "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while",
+    "with", "yield",
+];
+
+/// The prefixes which may precede a string literal, in lowercase.
+const STRING_PREFIXES: [&str; 8] = ["r", "u", "b", "f", "br", "rb", "fr", "rf"];
+
+/// Returns whether or not a character may begin an identifier.
+///
+/// * `character` - The character being checked.
+fn starts_identifier(character: char) -> bool {
+    character.is_alphabetic() || character == '_'
+}
+
+/// Returns whether or not a character may continue an identifier.
+///
+/// * `character` - The character being checked.
+fn continues_identifier(character: char) -> bool {
+    character.is_alphanumeric() || character == '_'
+}
+
+/// Returns the index following the end of the identifier beginning at a given index.
+///
+/// * `characters` - Every character in the file.
+/// * `start` - The index of the first character of the identifier.
+fn identifier_end(characters: &[char], start: usize) -> usize {
+    let mut end = start;
+    while end < characters.len() && continues_identifier(characters[end]) {
+        end += 1;
+    }
+    end
+}
+
+/// Returns the index of the first character at or after a given index which is not a space or tab.
+///
+/// * `characters` - Every character in the file.
+/// * `start` - The index from which to search.
+fn skip_spaces(characters: &[char], start: usize) -> usize {
+    let mut index = start;
+    while index < characters.len() && (characters[index] == ' ' || characters[index] == '\t') {
+        index += 1;
+    }
+    index
+}
+
+/// Finds the name assigned to by a statement beginning with an identifier, e.g. `total` in
+/// `total = 0` or `name` in `self.name: str = name`.
+///
+/// * `characters` - Every character in the file.
+/// * `start` - The index of the identifier at the start of the statement.
+fn assignment_target(characters: &[char], start: usize) -> Option<Range<usize>> {
+    let mut target = start..identifier_end(characters, start);
+
+    // Follows any attribute accesses, since only the final name is being defined.
+    let mut index = skip_spaces(characters, target.end);
+    while index < characters.len() && characters[index] == '.' {
+        let name_start = skip_spaces(characters, index + 1);
+        if name_start >= characters.len() || !starts_identifier(characters[name_start]) {
+            return None;
+        }
+        target = name_start..identifier_end(characters, name_start);
+        index = skip_spaces(characters, target.end);
+    }
+
+    let next = characters.get(index).copied();
+    let after_next = characters.get(index + 1).copied();
+    let is_assignment = (next == Some('=') && after_next != Some('='))
+        || (next == Some(':') && after_next != Some('='));
+    if is_assignment {
+        return Some(target);
+    }
+    None
+}
+
+/// Finds the end of a string literal, recording the ranges of its contents which are checked.
+///
+/// Escape sequences are skipped, as are the replacement fields of f-strings, which contain code.
+///
+/// * `characters` - Every character in the file.
+/// * `quote_start` - The index of the opening quote.
+/// * `is_f_string` - Whether or not the string has an `f` prefix.
+/// * `ranges` - The list of checked ranges to be populated.
+fn read_string(
+    characters: &[char],
+    quote_start: usize,
+    is_f_string: bool,
+    ranges: &mut Vec<Range<usize>>,
+) -> usize {
+    let quote = characters[quote_start];
+    let is_triple = characters.get(quote_start + 1) == Some(&quote)
+        && characters.get(quote_start + 2) == Some(&quote);
+    let quote_length = if is_triple { 3 } else { 1 };
+
+    let mut index = quote_start + quote_length;
+    let mut segment_start = index;
+    while index < characters.len() {
+        let character = characters[index];
+        if character == '\\' {
+            ranges.push(segment_start..index);
+            index = (index + 2).min(characters.len());
+            segment_start = index;
+            continue;
+        }
+        if character == '\n' && !is_triple {
+            break;
+        }
+        if character == quote
+            && (!is_triple
+                || (characters.get(index + 1) == Some(&quote)
+                    && characters.get(index + 2) == Some(&quote)))
+        {
+            ranges.push(segment_start..index);
+            return index + quote_length;
+        }
+        if is_f_string && character == '{' {
+            if characters.get(index + 1) == Some(&'{') {
+                index += 2;
+                continue;
+            }
+
+            // Skips the replacement field, which may itself contain braces.
+            ranges.push(segment_start..index);
+            let mut depth = 0;
+            while index < characters.len() {
+                match characters[index] {
+                    '{' => depth += 1,
+                    '}' => depth -= 1,
+                    _ => (),
+                }
+                index += 1;
+                if depth == 0 {
+                    break;
+                }
+            }
+            segment_start = index;
+            continue;
+        }
+        index += 1;
+    }
+
+    // The string is unterminated, so its contents end with the line or the file.
+    ranges.push(segment_start..index);
+    index
+}
+
+/// Finds the ranges of a Python file which are spell-checked: comments, docstrings and string
+/// literals, and optionally the names of the functions, classes and variables it defines.
+///
+/// * `characters` - Every character in the file.
+/// * `check_identifiers` - Whether or not to check the identifiers defined by the file.
+/// * `comments` - The list of comment ranges to be populated.
+pub fn checked_ranges(
+    characters: &[char],
+    check_identifiers: bool,
+    comments: &mut Vec<Range<usize>>,
+) -> Vec<Range<usize>> {
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+    let mut bracket_depth: usize = 0;
+    let mut at_statement_start = true;
+    let mut defining = false;
+
+    let mut index = 0;
+    while index < characters.len() {
+        let character = characters[index];
+
+        // Comments continue until the end of the line.
+        if character == '#' {
+            let mut end = index;
+            while end < characters.len() && characters[end] != '\n' {
+                end += 1;
+            }
+            ranges.push(index + 1..end);
+            comments.push(index + 1..end);
+            index = end;
+            continue;
+        }
+
+        if character == '\'' || character == '"' {
+            index = read_string(characters, index, false, &mut ranges);
+            at_statement_start = false;
+            continue;
+        }
+
+        if starts_identifier(character) {
+            let end = identifier_end(characters, index);
+            let name: String = characters[index..end].iter().collect();
+
+            // An identifier directly followed by a quote is the prefix of a string literal.
+            let next = characters.get(end).copied();
+            if (next == Some('\'') || next == Some('"'))
+                && STRING_PREFIXES.contains(&name.to_lowercase().as_str())
+            {
+                let is_f_string = name.to_lowercase().contains('f');
+                index = read_string(characters, end, is_f_string, &mut ranges);
+                at_statement_start = false;
+                continue;
+            }
+
+            if check_identifiers && !KEYWORDS.contains(&name.as_str()) {
+                if defining {
+                    ranges.push(index..end);
+                } else if at_statement_start {
+                    if let Some(target) = assignment_target(characters, index) {
+                        ranges.push(target);
+                    }
+                }
+            }
+            defining = name == "def" || name == "class";
+            at_statement_start = false;
+            index = end;
+            continue;
+        }
+
+        match character {
+            // Numbers (including any suffixes such as `0x1f` or `1e5j`) are skipped entirely.
+            '0'..='9' => {
+                index = identifier_end(characters, index);
+                at_statement_start = false;
+                continue;
+            }
+            '(' | '[' | '{' => bracket_depth += 1,
+            ')' | ']' | '}' => bracket_depth = bracket_depth.saturating_sub(1),
+            '\\' => {
+                // Line continuations do not end the statement.
+                index += 2;
+                continue;
+            }
+            '\n' | ';' if bracket_depth == 0 => {
+                at_statement_start = true;
+                defining = false;
+                index += 1;
+                continue;
+            }
+            _ => (),
+        }
+        if !character.is_whitespace() {
+            at_statement_start = false;
+        }
+        index += 1;
+    }
+
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the text of every checked range in a piece of Python source.
+    ///
+    /// * `source` - The Python source.
+    /// * `check_identifiers` - Whether or not to check the identifiers defined by the source.
+    fn checked_text(source: &str, check_identifiers: bool) -> Vec<String> {
+        let characters: Vec<char> = source.chars().collect();
+        checked_ranges(&characters, check_identifiers, &mut Vec::new())
+            .into_iter()
+            .filter(|range| !range.is_empty())
+            .map(|range| characters[range].iter().collect())
+            .collect()
+    }
+
+    /// Checks `checked_ranges` finds comments, docstrings and strings, but not code.
+    #[test]
+    fn checked_ranges_comments_and_strings() {
+        let source = "import numpy as np\n\ndef f(kwargs):\n    \"\"\"Docs here.\"\"\"\n    return np.x('text')  # note\n";
+        assert_eq!(
+            checked_text(source, false),
+            vec!["Docs here.", "text", " note"]
+        );
+    }
+
+    /// Checks `checked_ranges` skips escape sequences and f-string replacement fields.
+    #[test]
+    fn checked_ranges_escapes_and_f_strings() {
+        let source = "x = f'a\\nb {value!r:>{width}} c' + rb\"raw\"\n";
+        assert_eq!(checked_text(source, false), vec!["a", "b ", " c", "raw"]);
+    }
+
+    /// Checks `checked_ranges` finds the names of defined functions, classes and variables.
+    #[test]
+    fn checked_ranges_identifiers() {
+        let source = "class Foo:\n    def bar(self):\n        self.name: str = np.f(x)\n        if x == y:\n            pass\n";
+        assert_eq!(checked_text(source, true), vec!["Foo", "bar", "name"]);
+    }
+}
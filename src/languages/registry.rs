@@ -0,0 +1,126 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufRead, Read};
+use std::path::Path;
+
+use globset::GlobMatcher;
+
+use crate::config::config::Configuration;
+use crate::errors::all_errors::AntisepticError;
+use crate::languages::language::Language;
+use crate::overrides::compile_glob;
+use crate::overrides::glob_matches_file;
+
+/// The longest shebang line that is read when detecting the language of a file.
+const MAX_SHEBANG_LENGTH: u64 = 256;
+
+/// Determines which language frontend is used for each file.
+pub struct LanguageRegistry {
+    /// The language of files with each extension.
+    extensions: HashMap<&'static str, Language>,
+
+    /// The language of files matching each glob from `[language-overrides]`.
+    overrides: Vec<(GlobMatcher, Language)>,
+}
+
+impl LanguageRegistry {
+    /// Builds the registry of every known extension, along with the configured overrides.
+    ///
+    /// * `configuration` - The settings loaded from the configuration file.
+    pub fn new(configuration: &Configuration) -> Result<LanguageRegistry, AntisepticError> {
+        let extensions = HashMap::from([
+            ("txt", Language::PlainText),
+            ("py", Language::Python),
+            ("pyi", Language::Python),
+            ("pyw", Language::Python),
+            ("rs", Language::Rust),
+            ("md", Language::Markdown),
+            ("markdown", Language::Markdown),
+        ]);
+
+        let mut overrides = Vec::new();
+        for (glob_str, language) in &configuration.language_overrides {
+            overrides.push((compile_glob(glob_str)?, *language));
+        }
+
+        Ok(LanguageRegistry {
+            extensions,
+            overrides,
+        })
+    }
+
+    /// Determines the language of a file from, in order of precedence, the configured overrides,
+    /// its extension, and its shebang line. Files of an unknown language are plain text.
+    ///
+    /// * `file` - The path to the file.
+    pub fn detect(&self, file: &Path) -> Language {
+        if let Some((_, language)) = self
+            .overrides
+            .iter()
+            .find(|(matcher, _)| glob_matches_file(matcher, file))
+        {
+            return *language;
+        }
+
+        if let Some(extension) = file.extension().and_then(|extension| extension.to_str()) {
+            if let Some(language) = self.extensions.get(extension.to_lowercase().as_str()) {
+                return *language;
+            }
+        }
+
+        shebang_language(file).unwrap_or(Language::PlainText)
+    }
+}
+
+/// Determines the language of a script from the interpreter in its shebang line, e.g.
+/// `#!/usr/bin/env python3`.
+///
+/// * `file` - The path to the file.
+fn shebang_language(file: &Path) -> Option<Language> {
+    let open_file = File::open(file).ok()?;
+    let mut first_line = String::new();
+    io::BufReader::new(open_file.take(MAX_SHEBANG_LENGTH))
+        .read_line(&mut first_line)
+        .ok()?;
+    interpreter_language(&first_line)
+}
+
+/// Determines the language of an interpreter named in a shebang line.
+///
+/// * `line` - The first line of the file.
+fn interpreter_language(line: &str) -> Option<Language> {
+    let mut words = line.strip_prefix("#!")?.split_whitespace();
+    let mut interpreter = Path::new(words.next()?).file_name()?.to_str()?;
+
+    // With `env`, the interpreter is the first argument which is not an option.
+    if interpreter == "env" {
+        interpreter = words.find(|word| !word.starts_with('-'))?;
+    }
+
+    let name = interpreter.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.');
+    Language::from_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks `interpreter_language` recognizes interpreters given directly or through `env`.
+    #[test]
+    fn interpreter_language_shebangs() {
+        assert_eq!(
+            interpreter_language("#!/usr/bin/env python3\n"),
+            Some(Language::Python)
+        );
+        assert_eq!(
+            interpreter_language("#!/usr/bin/env -S python3.12 -u\n"),
+            Some(Language::Python)
+        );
+        assert_eq!(
+            interpreter_language("#!/usr/local/bin/python\n"),
+            Some(Language::Python)
+        );
+        assert_eq!(interpreter_language("#!/bin/bash\n"), None);
+        assert_eq!(interpreter_language("print()\n"), None);
+    }
+}
@@ -0,0 +1,239 @@
+use std::ops::Range;
+
+/// The prefixes which may precede a string literal.
+const STRING_PREFIXES: [&str; 5] = ["b", "c", "r", "br", "cr"];
+
+/// Returns whether or not a character may continue an identifier.
+///
+/// * `character` - The character being checked.
+fn continues_identifier(character: char) -> bool {
+    character.is_alphanumeric() || character == '_'
+}
+
+/// Returns the index following the end of the identifier beginning at a given index.
+///
+/// * `characters` - Every character in the file.
+/// * `start` - The index of the first character of the identifier.
+fn identifier_end(characters: &[char], start: usize) -> usize {
+    let mut end = start;
+    while end < characters.len() && continues_identifier(characters[end]) {
+        end += 1;
+    }
+    end
+}
+
+/// Finds the end of a block comment, which may contain nested block comments.
+///
+/// * `characters` - Every character in the file.
+/// * `start` - The index of the `/` opening the comment.
+fn block_comment_end(characters: &[char], start: usize) -> usize {
+    let mut depth = 0;
+    let mut index = start;
+    while index < characters.len() {
+        if characters[index] == '/' && characters.get(index + 1) == Some(&'*') {
+            depth += 1;
+            index += 2;
+        } else if characters[index] == '*' && characters.get(index + 1) == Some(&'/') {
+            depth -= 1;
+            index += 2;
+            if depth == 0 {
+                return index;
+            }
+        } else {
+            index += 1;
+        }
+    }
+    index
+}
+
+/// Finds the end of a string literal, recording the ranges of its contents which are checked.
+///
+/// * `characters` - Every character in the file.
+/// * `quote_start` - The index of the opening quote.
+/// * `ranges` - The list of checked ranges to be populated.
+fn read_string(characters: &[char], quote_start: usize, ranges: &mut Vec<Range<usize>>) -> usize {
+    let mut index = quote_start + 1;
+    let mut segment_start = index;
+    while index < characters.len() {
+        match characters[index] {
+            // Escape sequences are skipped, so that e.g. `\n` does not join the following word.
+            '\\' => {
+                ranges.push(segment_start..index);
+                index = (index + 2).min(characters.len());
+                segment_start = index;
+            }
+            '"' => {
+                ranges.push(segment_start..index);
+                return index + 1;
+            }
+            _ => index += 1,
+        }
+    }
+    ranges.push(segment_start..index);
+    index
+}
+
+/// Finds the end of a raw string literal, e.g. `r#"..."#`, recording its contents as checked.
+///
+/// * `characters` - Every character in the file.
+/// * `hashes_start` - The index following the `r` of the prefix.
+/// * `ranges` - The list of checked ranges to be populated.
+fn read_raw_string(
+    characters: &[char],
+    hashes_start: usize,
+    ranges: &mut Vec<Range<usize>>,
+) -> usize {
+    let mut index = hashes_start;
+    while index < characters.len() && characters[index] == '#' {
+        index += 1;
+    }
+    let hashes = index - hashes_start;
+    let content_start = index + 1;
+
+    // The string ends with a quote followed by as many hashes as it began with.
+    index = content_start;
+    while index < characters.len() {
+        if characters[index] == '"'
+            && characters[index + 1..]
+                .iter()
+                .take(hashes)
+                .filter(|character| **character == '#')
+                .count()
+                == hashes
+        {
+            ranges.push(content_start..index);
+            return index + 1 + hashes;
+        }
+        index += 1;
+    }
+    ranges.push(content_start.min(index)..index);
+    index
+}
+
+/// Finds the ranges of a Rust file which are spell-checked: comments (including doc comments) and
+/// string literals.
+///
+/// * `characters` - Every character in the file.
+/// * `comments` - The list of comment ranges to be populated.
+pub fn checked_ranges(characters: &[char], comments: &mut Vec<Range<usize>>) -> Vec<Range<usize>> {
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+
+    let mut index = 0;
+    while index < characters.len() {
+        let character = characters[index];
+        let next = characters.get(index + 1).copied();
+
+        // Line comments, including `///` and `//!` doc comments, continue until the end of the
+        // line.
+        if character == '/' && next == Some('/') {
+            let mut end = index;
+            while end < characters.len() && characters[end] != '\n' {
+                end += 1;
+            }
+            ranges.push(index + 2..end);
+            comments.push(index + 2..end);
+            index = end;
+            continue;
+        }
+
+        if character == '/' && next == Some('*') {
+            let end = block_comment_end(characters, index);
+            let is_terminated = end >= index + 4 && characters[end - 2..end] == ['*', '/'];
+            let content = index + 2..if is_terminated { end - 2 } else { end };
+            ranges.push(content.clone());
+            comments.push(content);
+            index = end;
+            continue;
+        }
+
+        if character == '"' {
+            index = read_string(characters, index, &mut ranges);
+            continue;
+        }
+
+        // Distinguishes character literals (e.g. `'a'` or `'\n'`) from lifetimes (e.g. `'a`),
+        // neither of which are checked.
+        if character == '\'' {
+            if next == Some('\\') {
+                index += 2;
+                while index < characters.len() && characters[index] != '\'' {
+                    index += 1;
+                }
+                index += 1;
+            } else if characters.get(index + 2) == Some(&'\'') {
+                index += 3;
+            } else {
+                index = identifier_end(characters, index + 1);
+            }
+            continue;
+        }
+
+        if character.is_alphabetic() || character == '_' {
+            let end = identifier_end(characters, index);
+            let name: String = characters[index..end].iter().collect();
+            let next = characters.get(end).copied();
+            if STRING_PREFIXES.contains(&name.as_str()) {
+                let is_raw = name.ends_with('r');
+                if is_raw && (next == Some('"') || next == Some('#')) {
+                    // Raw identifiers such as `r#type` are not strings.
+                    let mut quote = end;
+                    while quote < characters.len() && characters[quote] == '#' {
+                        quote += 1;
+                    }
+                    if characters.get(quote) == Some(&'"') {
+                        index = read_raw_string(characters, end, &mut ranges);
+                        continue;
+                    }
+                } else if next == Some('"') {
+                    index = read_string(characters, end, &mut ranges);
+                    continue;
+                }
+            }
+
+            // Identifiers (including raw identifiers) are never checked.
+            index = end;
+            if name == "r" && next == Some('#') {
+                index = identifier_end(characters, end + 1);
+            }
+            continue;
+        }
+
+        index += 1;
+    }
+
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the text of every checked range in a piece of Rust source.
+    ///
+    /// * `source` - The Rust source.
+    fn checked_text(source: &str) -> Vec<String> {
+        let characters: Vec<char> = source.chars().collect();
+        checked_ranges(&characters, &mut Vec::new())
+            .into_iter()
+            .filter(|range| !range.is_empty())
+            .map(|range| characters[range].iter().collect())
+            .collect()
+    }
+
+    /// Checks `checked_ranges` finds line, doc and nested block comments, but not code.
+    #[test]
+    fn checked_ranges_comments() {
+        let source = "//! Crate.\n/// Docs.\nfn f<'a>(x: &'a str) {} // end\n/* outer /* inner */ outer */ impl";
+        assert_eq!(
+            checked_text(source),
+            vec!["! Crate.", "/ Docs.", " end", " outer /* inner */ outer "]
+        );
+    }
+
+    /// Checks `checked_ranges` finds normal, byte and raw strings, but not character literals.
+    #[test]
+    fn checked_ranges_strings() {
+        let source = "let s = (\"a\\nb\", b\"byte\", r#\"raw \"q\" x\"#, '\"', '\\'', r#type);";
+        assert_eq!(checked_text(source), vec!["a", "b", "byte", "raw \"q\" x"]);
+    }
+}
@@ -1,10 +1,23 @@
+mod arguments;
+mod baseline;
 mod config;
+mod diagnostic;
+mod dialect;
+mod directives;
 mod errors;
 mod find_files;
+mod fix;
+mod languages;
+mod output;
+mod overrides;
+mod rules;
+mod skip;
 mod spellcheck;
+mod suggest;
 
 use std::borrow::BorrowMut;
 use std::collections::BTreeSet;
+use std::collections::HashMap;
 use std::collections::HashSet;
 use std::env;
 use std::fs;
@@ -20,8 +33,16 @@ use pyo3::types::PyList;
 use pyo3::types::PyString;
 use toml::Table;
 
+use crate::arguments::Arguments;
 use crate::config::config::load_config;
 use crate::config::config::Configuration;
+use crate::diagnostic::Diagnostic;
+use crate::dialect::DialectVariants;
+use crate::languages::registry::LanguageRegistry;
+use crate::output::emit::emit_diagnostics;
+use crate::overrides::OverrideMatcher;
+use crate::rules::Rule;
+use crate::skip::SkipPatterns;
 
 /// Parses the provided file as a TOML table.
 ///
@@ -29,19 +50,19 @@ use crate::config::config::Configuration;
 fn parse_file_as_toml(path_buffer: PathBuf) -> Result<Table, AntisepticError> {
     let path_str_result = path_buffer.to_str();
     if path_str_result.is_none() {
-        println!("{}", "Path buffer string parse failed.".red());
+        eprintln!("{}", "Path buffer string parse failed.".red());
         return Err(AntisepticError::StringParsingFailed);
     }
     let path = path_str_result.unwrap();
     let result = fs::read_to_string(path);
     if result.is_err() {
-        println!("Configuration file {} is not readable", path);
+        eprintln!("Configuration file {} is not readable", path);
         return Err(AntisepticError::ConfigFileCouldNotBeOpened);
     }
 
     let toml = result.unwrap().parse::<Table>();
     if toml.is_err() {
-        println!("Invalid configuration file: {}", path);
+        eprintln!("Invalid configuration file: {}", path);
         return Err(AntisepticError::InvalidConfigTOML);
     }
 
@@ -55,7 +76,7 @@ fn pyproject_get_config(path_buffer: PathBuf) -> Result<Table, AntisepticError>
     let contents = fs::read_to_string(path_buffer).expect("pyproject.toml not readable.");
     let table_option = contents.parse::<Table>();
     if table_option.is_err() {
-        println!("{}", "WARNING: Unparseable pyproject.toml found".yellow());
+        eprintln!("{}", "WARNING: Unparseable pyproject.toml found".yellow());
         return Err(AntisepticError::InvalidPyprojectTOML);
     }
     let table = table_option.unwrap();
@@ -77,10 +98,11 @@ fn pyproject_get_config(path_buffer: PathBuf) -> Result<Table, AntisepticError>
     Ok(antiseptic_table.unwrap())
 }
 
-/// Attempts to find the appropriate configuration file.
+/// Attempts to find the appropriate configuration file, returning its contents along with the
+/// directory containing it.
 ///
 /// * `path` - The path to the current working directory.
-fn find_config_in_dir(path: &Path) -> Result<Table, AntisepticError> {
+fn find_config_in_dir(path: &Path) -> Result<(Table, PathBuf), AntisepticError> {
     // Progressively iterates through the ancestors of the current working directory until the
     // configuration file is found, starting from the current working directory itself.
     let mut ancestors: Ancestors = path.ancestors();
@@ -92,13 +114,19 @@ fn find_config_in_dir(path: &Path) -> Result<Table, AntisepticError> {
         // `.antiseptic.toml` takes precedence over `antiseptic.toml` and `pyproject.toml`.
         let hidden_antiseptic_config = ancestor.join(".antiseptic.toml");
         if hidden_antiseptic_config.exists() {
-            return parse_file_as_toml(hidden_antiseptic_config);
+            return Ok((
+                parse_file_as_toml(hidden_antiseptic_config)?,
+                ancestor.to_path_buf(),
+            ));
         }
 
         // `antiseptic.toml` takes precedence over `pyproject.toml`.
         let antiseptic_config = ancestor.join("antiseptic.toml");
         if antiseptic_config.exists() {
-            return parse_file_as_toml(antiseptic_config);
+            return Ok((
+                parse_file_as_toml(antiseptic_config)?,
+                ancestor.to_path_buf(),
+            ));
         }
 
         // `pyproject.toml` can potentially be a valid Antiseptic configuration file if it contains
@@ -108,12 +136,12 @@ fn find_config_in_dir(path: &Path) -> Result<Table, AntisepticError> {
             continue;
         }
         let pyproject_config = pyproject_get_config(pyproject.clone());
-        if pyproject_config.is_ok() {
-            return pyproject_config;
+        if let Ok(result) = pyproject_config {
+            return Ok((result, ancestor.to_path_buf()));
         }
     }
 
-    return Err(AntisepticError::MissingConfig);
+    Err(AntisepticError::MissingConfig)
 }
 
 /// Returns a pointer to the path to the directory in which the Rust binary is kept.
@@ -121,12 +149,12 @@ fn find_config_in_dir(path: &Path) -> Result<Table, AntisepticError> {
 /// * `py_src_path` - The path provided by the Python interface.
 fn get_src_path(py_src_path: Option<&PyString>) -> Result<&Path, AntisepticError> {
     if py_src_path.is_none() {
-        println!("{}", "Faulty src path provided.".red());
+        eprintln!("{}", "Faulty src path provided.".red());
         return Err(AntisepticError::InvalidSrcPath);
     }
     let src_path_str = py_src_path.unwrap().to_str();
     if src_path_str.is_err() {
-        println!("{}", "Faulty src path provided.".red());
+        eprintln!("{}", "Faulty src path provided.".red());
         return Err(AntisepticError::InvalidSrcPath);
     }
     Ok(Path::new(src_path_str.unwrap()))
@@ -136,9 +164,11 @@ fn get_src_path(py_src_path: Option<&PyString>) -> Result<&Path, AntisepticError
 ///
 /// * `files` - The list of globs indicating which files to spell-check.
 /// * `py_src_path` - The path provided by the Python interface.
+/// * `arguments` - The options provided by the user on the command line.
 fn antiseptic_main(
     files: Option<&PyList>,
     py_src_path: Option<&PyString>,
+    arguments: &Arguments,
 ) -> Result<u64, AntisepticError> {
     // Gets the paths to the Rust binary, and the current working directory.
     let src_path = get_src_path(py_src_path)?;
@@ -148,68 +178,322 @@ fn antiseptic_main(
     };
 
     // Obtains a map from configuration keys to values.
-    let config_toml = match find_config_in_dir(&cwd) {
+    let (config_toml, config_dir) = match find_config_in_dir(&cwd) {
         Ok(result) => result,
         Err(error) => {
-            println!("{}", "No antiseptic configuration found.".red());
+            eprintln!("{}", "No antiseptic configuration found.".red());
             return Ok(error as u64);
         }
     };
 
     let mut configuration = Configuration {
+        config_dir,
         ..Default::default()
     };
     load_config(&config_toml, configuration.borrow_mut())?;
 
+    // Determines which rules are checked, where options given on the command line take precedence
+    // over the configuration. Setting `dialect` enables AS004, unless rules are selected explicitly.
+    let enabled_rules = Rule::enabled(
+        arguments
+            .select
+            .as_deref()
+            .or(configuration.select.as_deref()),
+        &[
+            &configuration.extend_select[..],
+            &arguments.extend_select[..],
+        ]
+        .concat(),
+        &[&configuration.ignore[..], &arguments.ignore[..]].concat(),
+        configuration.dialect.is_some(),
+    );
+
+    // Spellings which differ between dialects are checked against the dictionary as usual, unless
+    // AS004 is checked.
+    if !enabled_rules.contains(&Rule::DialectMismatch) {
+        configuration.dialect = None;
+    }
+
     // Obtains all files to be spell-checked.
     let mut all_files: BTreeSet<PathBuf> = BTreeSet::new();
     find_files::collect_all_files(files, all_files.borrow_mut(), &configuration)?;
 
+    // The baseline file records misspelled words, so is never itself spell-checked.
+    for path in [&arguments.baseline, &arguments.write_baseline]
+        .into_iter()
+        .flatten()
+    {
+        if let Ok(baseline_path) = path.canonicalize() {
+            all_files.retain(|file| {
+                file.canonicalize()
+                    .map_or(true, |file| file != baseline_path)
+            });
+        }
+    }
+
     // Obtains all words considered correct spellings.
     let mut words_allowed: HashSet<String> = spellcheck::get_word_set(src_path)?;
-    for word in configuration.allowed_words {
-        words_allowed.insert(word);
+    for word in &configuration.allowed_words {
+        words_allowed.insert(word.clone());
     }
 
     // Obtains all characters that are recognized as constituting a word, rather than punctuation.
     let characters_allowed: HashSet<char> = spellcheck::get_word_characters(src_path)?;
 
+    // Compiles the globs determining which files each override applies to.
+    let override_matcher = OverrideMatcher::new(&configuration.overrides)?;
+    let mut override_word_sets: HashMap<Vec<usize>, HashSet<String>> = HashMap::new();
+    let mut file_overrides: HashMap<PathBuf, Vec<usize>> = HashMap::new();
+
+    // Determines which language frontend is used for each file.
+    let language_registry = LanguageRegistry::new(&configuration)?;
+    let skip_patterns = SkipPatterns::new(&configuration.skip, &configuration.ignore_regex);
+
+    // Obtains the British and American spellings of each word, if dialects are distinguished.
+    let dialect_variants = match configuration.dialect {
+        Some(_) => DialectVariants::load(src_path)?,
+        None => DialectVariants::default(),
+    };
+
     // Iterates over every file (only stopping if an unexpected error occurs.)
-    let mut found_mistake = false;
+    let mut diagnostics: Vec<Diagnostic> = Vec::new();
+    let mut invalid_files: HashSet<PathBuf> = HashSet::new();
     for file in &all_files {
-        match spellcheck::read_file(file, &characters_allowed, &words_allowed) {
+        // Resolves the words allowed in the file, reusing the set built for any other file with
+        // the same overrides.
+        let matched = override_matcher.matching(file);
+        let file_words_allowed = if overrides::changes_words(&matched, &configuration.overrides) {
+            if !override_word_sets.contains_key(&matched) {
+                let word_set = overrides::build_word_set(
+                    &matched,
+                    &configuration.overrides,
+                    &words_allowed,
+                    &configuration.allowed_words,
+                    &configuration.config_dir,
+                )?;
+                override_word_sets.insert(matched.clone(), word_set);
+            }
+            file_overrides.insert(file.clone(), matched.clone());
+            &override_word_sets[&matched]
+        } else {
+            &words_allowed
+        };
+
+        let file_start = diagnostics.len();
+        let language = language_registry.detect(file);
+        match spellcheck::read_file(
+            file,
+            &language,
+            &configuration,
+            &skip_patterns,
+            &characters_allowed,
+            file_words_allowed,
+            &dialect_variants,
+            diagnostics.borrow_mut(),
+        ) {
             Ok(_result) => (),
-            Err(e) if e == AntisepticError::CheckedFileIsNotUTF8 => println!(
-                "{}{}{}",
-                "WARNING: ".yellow(),
-                file.to_string_lossy().yellow(),
-                " did not contain valid UTF-8.".yellow()
-            ),
-            Err(e) if e == AntisepticError::SpellingMistakeFound => {
-                found_mistake = true;
+            Err(AntisepticError::CheckedFileIsNotUTF8) => {
+                eprintln!(
+                    "{}{}{}",
+                    "WARNING: ".yellow(),
+                    file.to_string_lossy().yellow(),
+                    " did not contain valid UTF-8.".yellow()
+                );
+                invalid_files.insert(file.clone());
             }
+            // Spelling mistakes are recorded in `diagnostics`.
+            Err(AntisepticError::SpellingMistakeFound) => (),
             Err(e) => return Err(e),
         }
+
+        // Removes diagnostics for any rules the overrides ignore in the file.
+        let ignored = overrides::ignored_rules(&matched, &configuration.overrides);
+        if !ignored.is_empty() {
+            let file_diagnostics = diagnostics.split_off(file_start);
+            diagnostics.extend(
+                file_diagnostics
+                    .into_iter()
+                    .filter(|diagnostic| !ignored.contains(&diagnostic.rule)),
+            );
+        }
     }
 
+    // Removes diagnostics for any rules which are not selected.
+    diagnostics.retain(|diagnostic| enabled_rules.contains(&diagnostic.rule));
+
+    // Words from `forbidden-words` are reported by their own rule, so are never also spelling
+    // mistakes.
+    let forbidden_words: HashSet<&String> = configuration
+        .forbidden_words
+        .iter()
+        .flat_map(|forbidden_word| &forbidden_word.words)
+        .collect();
+    diagnostics.retain(|diagnostic| {
+        diagnostic.rule != Rule::SpellingMistake
+            || !forbidden_words.contains(&diagnostic.word.to_lowercase())
+    });
+
+    // Records every diagnostic in a new baseline, instead of displaying them. Suggestions are not
+    // recorded, so are never searched for.
+    if let Some(path) = &arguments.write_baseline {
+        let written = baseline::write_baseline(path, &diagnostics)?;
+        eprintln!(
+            "Recorded {} problem(s) in baseline {}.",
+            written,
+            path.to_string_lossy()
+        );
+        return Ok(0);
+    }
+
+    // Removes diagnostics recorded in the baseline, reporting any entries which are now stale.
+    if let Some(path) = &arguments.baseline {
+        let entries = baseline::load_baseline(path)?;
+        let (remaining, stale) = baseline::apply_baseline(entries, diagnostics, &all_files)?;
+        baseline::print_stale_entries(&stale);
+        diagnostics = remaining;
+    }
+
+    // Suggests corrections for each spelling mistake. This is done last, since searching for
+    // suggestions is slow and is wasted on any diagnostics the baseline suppresses.
+    suggest::attach_suggestions(
+        diagnostics.borrow_mut(),
+        &words_allowed,
+        &override_word_sets,
+        &file_overrides,
+        configuration.max_suggestions,
+        arguments.fix || arguments.diff,
+    );
+
+    // Displays the changes that fixing would make, without writing them.
+    if arguments.diff {
+        let fix_result = fix::fix_all(diagnostics, &invalid_files)?;
+        print!("{}", fix::unified_diff(&fix_result.changes));
+        if fix_result.changes.is_empty() {
+            return Ok(0);
+        }
+        eprintln!("Would fix {} problem(s).", fix_result.fixed.len());
+        return Err(AntisepticError::SpellingMistakeFound);
+    }
+
+    // Rewrites files with any unambiguous corrections, leaving only unfixed diagnostics.
+    if arguments.fix {
+        let fix_result = fix::fix_all(diagnostics, &invalid_files)?;
+        fix::write_changes(&fix_result.changes)?;
+        fix::print_fixes(&fix_result.fixed, arguments.column_mode);
+        diagnostics = fix_result.remaining;
+    }
+
+    // Displays every diagnostic found across all files at once.
+    emit_diagnostics(&diagnostics, arguments.output_format, arguments.column_mode);
+
     // Indicates that a spelling mistake was found, if necessary.
-    if found_mistake {
+    if !diagnostics.is_empty() {
         return Err(AntisepticError::SpellingMistakeFound);
     }
 
     Ok(0)
 }
 
+/// Displays the documentation of a rule, for `--explain`.
+///
+/// * `code` - The code of the rule (e.g. `AS001`).
+fn explain_rule(code: &str) -> Result<u64, AntisepticError> {
+    let rule = match Rule::from_code(code) {
+        Some(result) => result,
+        None => {
+            eprintln!("{}{}", "Unknown rule code: ".red(), code.red());
+            return Err(AntisepticError::UnknownRuleCode);
+        }
+    };
+    println!("# {} ({})", rule.name(), rule.code());
+    println!();
+    println!(
+        "{}",
+        if rule.default_enabled() {
+            "This rule is enabled by default."
+        } else {
+            "This rule is disabled by default."
+        }
+    );
+    println!();
+    println!("{}", rule.documentation());
+    Ok(0)
+}
+
 /// The main entry point for Antiseptic.
 ///
 /// * `files` - The list of globs indicating which files to spell-check.
 /// * `py_src_path` - The path provided by the Python interface.
+/// * `output_format` - The name of the format in which diagnostics are displayed.
+/// * `column_mode` - The name of the units in which columns are counted.
+/// * `fix` - Whether or not to rewrite files with unambiguous corrections.
+/// * `diff` - Whether or not to display the changes fixing would make, without writing them.
+/// * `select` - The rule codes which are checked, replacing `select` from the configuration.
+/// * `extend_select` - Further rule codes which are checked.
+/// * `ignore` - Further rule codes which are not checked.
+/// * `explain` - The code of a rule whose documentation is displayed instead of spell-checking.
+/// * `baseline` - The baseline file whose recorded diagnostics are suppressed.
+/// * `write_baseline` - The baseline file in which every diagnostic is recorded, instead of being
+///   displayed.
 #[pyfunction]
-fn antiseptic(files: Option<&PyList>, py_src_path: Option<&PyString>) -> PyResult<u64> {
-    return match antiseptic_main(files, py_src_path) {
+#[pyo3(signature = (
+    files,
+    py_src_path,
+    output_format = None,
+    column_mode = None,
+    fix = false,
+    diff = false,
+    select = None,
+    extend_select = None,
+    ignore = None,
+    explain = None,
+    baseline = None,
+    write_baseline = None
+))]
+#[allow(clippy::too_many_arguments)]
+fn antiseptic(
+    files: Option<&PyList>,
+    py_src_path: Option<&PyString>,
+    output_format: Option<&PyString>,
+    column_mode: Option<&PyString>,
+    fix: bool,
+    diff: bool,
+    select: Option<&PyList>,
+    extend_select: Option<&PyList>,
+    ignore: Option<&PyList>,
+    explain: Option<&PyString>,
+    baseline: Option<&PyString>,
+    write_baseline: Option<&PyString>,
+) -> PyResult<u64> {
+    if let Some(code) = explain {
+        let result = match code.to_str() {
+            Ok(code_str) => explain_rule(code_str),
+            Err(_e) => Err(AntisepticError::StringParsingFailed),
+        };
+        return match result {
+            Ok(result) => Ok(result),
+            Err(error) => Ok(error as u64),
+        };
+    }
+
+    let arguments = match Arguments::from_python(
+        output_format,
+        column_mode,
+        fix,
+        diff,
+        select,
+        extend_select,
+        ignore,
+        baseline,
+        write_baseline,
+    ) {
+        Ok(result) => result,
+        Err(error) => return Ok(error as u64),
+    };
+    match antiseptic_main(files, py_src_path, &arguments) {
         Ok(result) => Ok(result),
         Err(error) => Ok(error as u64),
-    };
+    }
 }
 
 /// A Python module implemented in Rust.
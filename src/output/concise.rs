@@ -0,0 +1,25 @@
+use colored::Colorize;
+
+use crate::diagnostic::ColumnMode;
+use crate::diagnostic::Diagnostic;
+
+/// Prints each diagnostic on its own line, matching the style of Ruff's concise output.
+///
+/// * `diagnostics` - Every problem found during the spell-check.
+/// * `column_mode` - The units in which columns are counted.
+pub fn print_diagnostics(diagnostics: &[Diagnostic], column_mode: ColumnMode) {
+    for diagnostic in diagnostics {
+        let position = &diagnostic.position;
+        println!(
+            "{}{}{}{}{}{} {} {}",
+            position.file.to_string_lossy().bold(),
+            ":".cyan(),
+            position.line_no,
+            ":".cyan(),
+            position.column(column_mode),
+            ":".cyan(),
+            diagnostic.rule.code().red().bold(),
+            diagnostic.message()
+        );
+    }
+}
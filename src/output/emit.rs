@@ -0,0 +1,73 @@
+use colored::Colorize;
+
+use crate::diagnostic::ColumnMode;
+use crate::diagnostic::Diagnostic;
+use crate::errors::all_errors::AntisepticError;
+use crate::output::concise;
+use crate::output::github;
+use crate::output::json;
+use crate::output::sarif;
+
+/// Every format in which Antiseptic can display its diagnostics.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OutputFormat {
+    /// One colored line per diagnostic, in the style of Ruff's concise output.
+    Concise,
+
+    /// A single JSON array containing every diagnostic.
+    Json,
+
+    /// A SARIF 2.1.0 log, as ingested by code-scanning dashboards.
+    Sarif,
+
+    /// GitHub Actions workflow commands, which annotate pull request diffs.
+    Github,
+}
+
+impl OutputFormat {
+    /// Returns whether or not the output format can count columns in the given units.
+    ///
+    /// * `column_mode` - The units in which columns are counted.
+    pub fn supports_column_mode(&self, column_mode: ColumnMode) -> bool {
+        // SARIF only supports columns counted in Unicode code points or UTF-16 code units.
+        !(*self == OutputFormat::Sarif && column_mode == ColumnMode::Byte)
+    }
+
+    /// Obtains the output format from its name as given on the command line.
+    ///
+    /// * `name` - The name of the output format (e.g. `json`).
+    pub fn from_name(name: &str) -> Result<OutputFormat, AntisepticError> {
+        match name {
+            "concise" => Ok(OutputFormat::Concise),
+            "json" => Ok(OutputFormat::Json),
+            "sarif" => Ok(OutputFormat::Sarif),
+            "github" => Ok(OutputFormat::Github),
+            _ => {
+                eprintln!("{}{}", "Unknown output format: ".red(), name.red());
+                Err(AntisepticError::InvalidOutputFormat)
+            }
+        }
+    }
+}
+
+/// Displays every diagnostic in the requested format.
+///
+/// Diagnostics are the only output written to stdout. Every other message, such as an error, a
+/// warning or a report of the fixes applied, is written to stderr, so that machine-readable
+/// formats can be parsed from stdout.
+///
+/// * `diagnostics` - Every problem found during the spell-check.
+/// * `output_format` - The format in which the diagnostics should be displayed.
+/// * `column_mode` - The units in which columns are counted.
+pub fn emit_diagnostics(
+    diagnostics: &[Diagnostic],
+    output_format: OutputFormat,
+    column_mode: ColumnMode,
+) {
+    match output_format {
+        OutputFormat::Concise => concise::print_diagnostics(diagnostics, column_mode),
+        OutputFormat::Json => json::print_diagnostics(diagnostics, column_mode),
+        OutputFormat::Sarif => sarif::print_diagnostics(diagnostics, column_mode),
+        OutputFormat::Github => github::print_diagnostics(diagnostics, column_mode),
+    }
+}
@@ -0,0 +1,71 @@
+use crate::diagnostic::ColumnMode;
+use crate::diagnostic::Diagnostic;
+
+/// Escapes the message of a workflow command, as required by GitHub Actions.
+///
+/// * `data` - The text to be escaped.
+fn escape_data(data: &str) -> String {
+    data.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escapes a property of a workflow command (e.g. the file name), which additionally may not
+/// contain colons or commas.
+///
+/// * `property` - The text to be escaped.
+fn escape_property(property: &str) -> String {
+    escape_data(property)
+        .replace(':', "%3A")
+        .replace(',', "%2C")
+}
+
+/// Formats a diagnostic as a GitHub Actions `::error` workflow command.
+///
+/// * `diagnostic` - The problem to be formatted.
+/// * `column_mode` - The units in which columns are counted.
+fn diagnostic_to_github(diagnostic: &Diagnostic, column_mode: ColumnMode) -> String {
+    let position = &diagnostic.position;
+    format!(
+        "::error title={},file={},line={},endLine={},col={},endColumn={}::{}",
+        escape_property(&format!("Antiseptic ({})", diagnostic.rule.code())),
+        escape_property(&position.file.to_string_lossy()),
+        position.line_no,
+        diagnostic.end_line_no,
+        position.column(column_mode),
+        diagnostic.end_column(column_mode),
+        escape_data(&format!(
+            "{} {}",
+            diagnostic.rule.code(),
+            diagnostic.message()
+        ))
+    )
+}
+
+/// Prints each diagnostic as a workflow command, so that GitHub Actions annotates the offending
+/// lines on pull request diffs.
+///
+/// * `diagnostics` - Every problem found during the spell-check.
+/// * `column_mode` - The units in which columns are counted.
+pub fn print_diagnostics(diagnostics: &[Diagnostic], column_mode: ColumnMode) {
+    for diagnostic in diagnostics {
+        println!("{}", diagnostic_to_github(diagnostic, column_mode));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::Rule;
+
+    /// Checks `diagnostic_to_github` produces an escaped `::error` command.
+    #[test]
+    fn diagnostic_to_github_escaped() {
+        let diagnostic = Diagnostic::at("odd,name.txt", 15, 32, Rule::SpellingMistake, "helol");
+        assert_eq!(
+            diagnostic_to_github(&diagnostic, ColumnMode::Char),
+            "::error title=Antiseptic (AS001),file=odd%2Cname.txt,line=15,endLine=15,col=32,\
+             endColumn=37::AS001 spelling mistake `helol`"
+        );
+    }
+}
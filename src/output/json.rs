@@ -0,0 +1,82 @@
+use serde_json::json;
+use serde_json::Value;
+
+use crate::diagnostic::ColumnMode;
+use crate::diagnostic::Diagnostic;
+
+/// Converts a diagnostic into a JSON object.
+///
+/// * `diagnostic` - The problem to be converted.
+/// * `column_mode` - The units in which columns are counted.
+fn diagnostic_to_json(diagnostic: &Diagnostic, column_mode: ColumnMode) -> Value {
+    let position = &diagnostic.position;
+    json!({
+        "code": diagnostic.rule.code(),
+        "message": diagnostic.message(),
+        "filename": position.file.to_string_lossy(),
+        "location": {
+            "row": position.line_no,
+            "column": position.column(column_mode),
+        },
+        "end_location": {
+            "row": diagnostic.end_line_no,
+            "column": diagnostic.end_column(column_mode),
+        },
+        "word": diagnostic.word,
+        "token": diagnostic.token,
+        "suggestions": diagnostic.suggestions,
+        "fix": diagnostic.fix,
+    })
+}
+
+/// Prints every diagnostic as a single JSON array.
+///
+/// * `diagnostics` - Every problem found during the spell-check.
+/// * `column_mode` - The units in which columns are counted.
+pub fn print_diagnostics(diagnostics: &[Diagnostic], column_mode: ColumnMode) {
+    let array = Value::Array(
+        diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic_to_json(diagnostic, column_mode))
+            .collect(),
+    );
+    println!("{}", serde_json::to_string_pretty(&array).unwrap());
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::diagnostic::ReadPosition;
+    use crate::rules::Rule;
+
+    /// Checks `diagnostic_to_json` includes the position, rule, word and token.
+    #[test]
+    fn diagnostic_to_json_fields() {
+        let position = ReadPosition {
+            file: PathBuf::from("myfile.txt"),
+            line_no: 15,
+            char_no: 32,
+            byte_no: 35,
+            utf16_no: 33,
+        };
+        let diagnostic = Diagnostic {
+            suggestions: vec!["Right".to_owned()],
+            ..Diagnostic::new(position, Rule::SpellingMistake, "Rigth", "leftRigth")
+        };
+        let value = diagnostic_to_json(&diagnostic, ColumnMode::Char);
+        assert_eq!(value["code"], "AS001");
+        assert_eq!(value["filename"], "myfile.txt");
+        assert_eq!(value["location"]["row"], 15);
+        assert_eq!(value["location"]["column"], 32);
+        assert_eq!(value["end_location"]["column"], 37);
+        assert_eq!(value["word"], "Rigth");
+        assert_eq!(value["token"], "leftRigth");
+        assert_eq!(value["suggestions"][0], "Right");
+
+        let value = diagnostic_to_json(&diagnostic, ColumnMode::Byte);
+        assert_eq!(value["location"]["column"], 35);
+        assert_eq!(value["end_location"]["column"], 40);
+    }
+}
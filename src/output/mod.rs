@@ -0,0 +1,5 @@
+pub mod concise;
+pub mod emit;
+pub mod github;
+pub mod json;
+pub mod sarif;
@@ -0,0 +1,109 @@
+use serde_json::json;
+use serde_json::Value;
+
+use crate::diagnostic::ColumnMode;
+use crate::diagnostic::Diagnostic;
+use crate::rules::Rule;
+
+/// Converts a rule into a SARIF reporting descriptor, for use in `tool.driver.rules`.
+///
+/// * `rule` - The rule to be described.
+fn rule_to_sarif(rule: &Rule) -> Value {
+    json!({
+        "id": rule.code(),
+        "name": rule.name(),
+        "shortDescription": {
+            "text": rule.description(),
+        },
+        "defaultConfiguration": {
+            "level": "error",
+        },
+    })
+}
+
+/// Converts a diagnostic into a SARIF result.
+///
+/// * `diagnostic` - The problem to be converted.
+/// * `column_mode` - The units in which columns are counted.
+fn diagnostic_to_sarif(diagnostic: &Diagnostic, column_mode: ColumnMode) -> Value {
+    let position = &diagnostic.position;
+
+    // SARIF expects URIs, so paths always use forward slashes and omit any leading `./`.
+    let path = position.file.to_string_lossy().replace('\\', "/");
+    let uri = path.strip_prefix("./").unwrap_or(&path);
+
+    let rule_index = Rule::ALL.iter().position(|rule| *rule == diagnostic.rule);
+    json!({
+        "ruleId": diagnostic.rule.code(),
+        "ruleIndex": rule_index,
+        "level": "error",
+        "message": {
+            "text": diagnostic.message(),
+        },
+        "locations": [{
+            "physicalLocation": {
+                "artifactLocation": {
+                    "uri": uri,
+                },
+                "region": {
+                    "startLine": position.line_no,
+                    "startColumn": position.column(column_mode),
+                    "endLine": diagnostic.end_line_no,
+                    "endColumn": diagnostic.end_column(column_mode),
+                },
+            },
+        }],
+    })
+}
+
+/// Prints every diagnostic as a SARIF 2.1.0 log.
+///
+/// * `diagnostics` - Every problem found during the spell-check.
+/// * `column_mode` - The units in which columns are counted, which may not be bytes since SARIF
+///   only supports Unicode code points and UTF-16 code units.
+pub fn print_diagnostics(diagnostics: &[Diagnostic], column_mode: ColumnMode) {
+    let column_kind = match column_mode {
+        ColumnMode::Utf16 => "utf16CodeUnits",
+        _ => "unicodeCodePoints",
+    };
+    let log = json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "antiseptic",
+                    "informationUri": "https://github.com/clockback/antiseptic",
+                    "rules": Rule::ALL.iter().map(rule_to_sarif).collect::<Vec<Value>>(),
+                },
+            },
+            "columnKind": column_kind,
+            "results": diagnostics
+                .iter()
+                .map(|diagnostic| diagnostic_to_sarif(diagnostic, column_mode))
+                .collect::<Vec<Value>>(),
+        }],
+    });
+    println!("{}", serde_json::to_string_pretty(&log).unwrap());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks `diagnostic_to_sarif` maps the position to a physical location.
+    #[test]
+    fn diagnostic_to_sarif_location() {
+        let diagnostic =
+            Diagnostic::at("./docs/myfile.txt", 15, 32, Rule::SpellingMistake, "helol");
+        let value = diagnostic_to_sarif(&diagnostic, ColumnMode::Char);
+        let location = &value["locations"][0]["physicalLocation"];
+        assert_eq!(value["ruleId"], "AS001");
+        assert_eq!(value["ruleIndex"], 0);
+        assert_eq!(location["artifactLocation"]["uri"], "docs/myfile.txt");
+        assert_eq!(location["region"]["startLine"], 15);
+        assert_eq!(location["region"]["startColumn"], 32);
+        assert_eq!(location["region"]["endLine"], 15);
+        assert_eq!(location["region"]["endColumn"], 37);
+    }
+}
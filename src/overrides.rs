@@ -0,0 +1,189 @@
+use std::collections::HashSet;
+use std::path::Path;
+
+use colored::Colorize;
+use globset::Glob;
+use globset::GlobMatcher;
+
+use crate::config::config::Override;
+use crate::errors::all_errors::AntisepticError;
+use crate::rules::Rule;
+use crate::spellcheck;
+
+/// Compiles a glob from the configuration file.
+///
+/// * `glob_str` - The glob, e.g. `tests/**`.
+pub fn compile_glob(glob_str: &str) -> Result<GlobMatcher, AntisepticError> {
+    match Glob::new(glob_str) {
+        Ok(glob) => Ok(glob.compile_matcher()),
+        Err(_e) => {
+            eprintln!("{}{}", "Invalid glob: ".red(), glob_str.red());
+            Err(AntisepticError::InvalidGlob)
+        }
+    }
+}
+
+/// Returns whether or not a glob matches a file, either by the file's path (with or without a
+/// leading `./`) or by the file's name.
+///
+/// * `matcher` - The compiled glob.
+/// * `file` - The path to the file.
+pub fn glob_matches_file(matcher: &GlobMatcher, file: &Path) -> bool {
+    let relative = file.strip_prefix("./").unwrap_or(file);
+    matcher.is_match(file)
+        || matcher.is_match(relative)
+        || file
+            .file_name()
+            .is_some_and(|name| matcher.is_match(Path::new(name)))
+}
+
+/// Matches files against the globs of every override in the configuration.
+pub struct OverrideMatcher {
+    /// The compiled globs of each override, in the same order as the overrides.
+    matchers: Vec<Vec<GlobMatcher>>,
+}
+
+impl OverrideMatcher {
+    /// Compiles the globs of every override.
+    ///
+    /// * `overrides` - Every override in the configuration.
+    pub fn new(overrides: &[Override]) -> Result<OverrideMatcher, AntisepticError> {
+        let mut matchers = Vec::new();
+        for override_settings in overrides {
+            let mut override_matchers = Vec::new();
+            for glob_str in &override_settings.files {
+                override_matchers.push(compile_glob(glob_str)?);
+            }
+            matchers.push(override_matchers);
+        }
+        Ok(OverrideMatcher { matchers })
+    }
+
+    /// Returns the indices of every override applying to a file.
+    ///
+    /// * `file` - The path to the file.
+    pub fn matching(&self, file: &Path) -> Vec<usize> {
+        let mut indices = Vec::new();
+        for (index, override_matchers) in self.matchers.iter().enumerate() {
+            if override_matchers
+                .iter()
+                .any(|matcher| glob_matches_file(matcher, file))
+            {
+                indices.push(index);
+            }
+        }
+        indices
+    }
+}
+
+/// Returns whether or not any of the matched overrides change which words are allowed.
+///
+/// * `matched` - The indices of the overrides applying to a file.
+/// * `overrides` - Every override in the configuration.
+pub fn changes_words(matched: &[usize], overrides: &[Override]) -> bool {
+    matched.iter().any(|index| {
+        !overrides[*index].allowed_words.is_empty() || overrides[*index].dictionary.is_some()
+    })
+}
+
+/// Builds the set of words allowed in a file to which overrides apply.
+///
+/// If several overrides specify a dictionary, the last one takes precedence. The allowed words of
+/// every override are combined with the global `allowed-words`.
+///
+/// * `matched` - The indices of the overrides applying to the file.
+/// * `overrides` - Every override in the configuration.
+/// * `default_words` - The set of words allowed in files without overrides.
+/// * `global_allowed_words` - The words from the global `allowed-words` setting.
+/// * `config_dir` - The directory containing the configuration file, against which the path to
+///   the dictionary is resolved.
+pub fn build_word_set(
+    matched: &[usize],
+    overrides: &[Override],
+    default_words: &HashSet<String>,
+    global_allowed_words: &[String],
+    config_dir: &Path,
+) -> Result<HashSet<String>, AntisepticError> {
+    let dictionary = matched
+        .iter()
+        .rev()
+        .find_map(|index| overrides[*index].dictionary.as_ref());
+    let mut words_allowed = match dictionary {
+        Some(path) => {
+            let mut words = spellcheck::get_word_set_from_file(&config_dir.join(path))?;
+            words.extend(global_allowed_words.iter().cloned());
+            words
+        }
+        None => default_words.clone(),
+    };
+    for index in matched {
+        words_allowed.extend(overrides[*index].allowed_words.iter().cloned());
+    }
+    Ok(words_allowed)
+}
+
+/// Returns every rule ignored by the overrides applying to a file.
+///
+/// * `matched` - The indices of the overrides applying to the file.
+/// * `overrides` - Every override in the configuration.
+pub fn ignored_rules(matched: &[usize], overrides: &[Override]) -> HashSet<Rule> {
+    matched
+        .iter()
+        .flat_map(|index| overrides[*index].ignore.iter().copied())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    /// Checks `matching` finds overrides by path, with or without a leading `./`, and by name.
+    #[test]
+    fn matching_paths() {
+        let overrides = vec![
+            Override {
+                files: vec!["tests/**".to_owned()],
+                ..Default::default()
+            },
+            Override {
+                files: vec!["*.md".to_owned()],
+                ..Default::default()
+            },
+        ];
+        let matcher = OverrideMatcher::new(&overrides).unwrap();
+        assert_eq!(matcher.matching(&PathBuf::from("./tests/a.txt")), vec![0]);
+        assert_eq!(
+            matcher.matching(&PathBuf::from("tests/README.md")),
+            vec![0, 1]
+        );
+        assert!(matcher.matching(&PathBuf::from("./src/a.txt")).is_empty());
+    }
+
+    /// Checks `build_word_set` resolves the dictionary against the directory containing the
+    /// configuration file, rather than the working directory.
+    #[test]
+    fn build_word_set_dictionary_path() {
+        let config_dir = std::env::temp_dir().join("antiseptic_build_word_set_dictionary_path");
+        std::fs::create_dir_all(config_dir.join("dictionaries")).unwrap();
+        std::fs::write(config_dir.join("dictionaries/test.txt"), "glubbage\n").unwrap();
+        let overrides = vec![Override {
+            files: vec!["tests/**".to_owned()],
+            allowed_words: vec!["glimp".to_owned()],
+            dictionary: Some("dictionaries/test.txt".to_owned()),
+            ..Default::default()
+        }];
+        let result = build_word_set(
+            &[0],
+            &overrides,
+            &HashSet::new(),
+            &["hello".to_owned()],
+            &config_dir,
+        );
+        std::fs::remove_dir_all(&config_dir).unwrap();
+        let mut words: Vec<String> = result.unwrap().into_iter().collect();
+        words.sort();
+        assert_eq!(words, vec!["glimp", "glubbage", "hello"]);
+    }
+}
@@ -0,0 +1,302 @@
+/// Every rule that Antiseptic is able to check for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Rule {
+    /// A word is not found in the dictionary.
+    SpellingMistake,
+
+    /// A word is immediately repeated, e.g. "the the".
+    RepeatedWord,
+
+    /// A word or phrase from `forbidden-words` is used.
+    ForbiddenWord,
+
+    /// A word is spelled as in a different dialect from the one expected.
+    DialectMismatch,
+}
+
+impl Rule {
+    /// Every rule, in order of its code.
+    pub const ALL: [Rule; 4] = [
+        Rule::SpellingMistake,
+        Rule::RepeatedWord,
+        Rule::ForbiddenWord,
+        Rule::DialectMismatch,
+    ];
+
+    /// Returns the rule identified by a code, if there is one.
+    ///
+    /// * `code` - The code of the rule (e.g. `AS001`).
+    pub fn from_code(code: &str) -> Option<Rule> {
+        Rule::ALL.into_iter().find(|rule| rule.code() == code)
+    }
+
+    /// Returns every rule matched by a selector, which is either `ALL` or a prefix of the codes of
+    /// the rules (e.g. `AS001`, or `AS00` for every rule from `AS001` to `AS009`). Any other
+    /// selector, including one shorter than `AS`, matches no rules.
+    ///
+    /// * `selector` - The selector, as given in `select`, `extend-select` or `ignore`.
+    pub fn from_selector(selector: &str) -> Vec<Rule> {
+        if selector == "ALL" {
+            return Rule::ALL.to_vec();
+        }
+        let is_prefix = match selector.strip_prefix("AS") {
+            Some(digits) => digits.chars().all(|character| character.is_ascii_digit()),
+            None => false,
+        };
+        if !is_prefix {
+            return Vec::new();
+        }
+        Rule::ALL
+            .into_iter()
+            .filter(|rule| rule.code().starts_with(selector))
+            .collect()
+    }
+
+    /// Returns the code by which the rule is identified (e.g. `AS001`).
+    pub fn code(&self) -> &'static str {
+        match self {
+            Rule::SpellingMistake => "AS001",
+            Rule::RepeatedWord => "AS002",
+            Rule::ForbiddenWord => "AS003",
+            Rule::DialectMismatch => "AS004",
+        }
+    }
+
+    /// Returns the kebab-case name of the rule (e.g. `spelling-mistake`).
+    pub fn name(&self) -> &'static str {
+        match self {
+            Rule::SpellingMistake => "spelling-mistake",
+            Rule::RepeatedWord => "repeated-word",
+            Rule::ForbiddenWord => "forbidden-word",
+            Rule::DialectMismatch => "dialect-mismatch",
+        }
+    }
+
+    /// Returns a one-sentence summary of what the rule checks for.
+    pub fn description(&self) -> &'static str {
+        match self {
+            Rule::SpellingMistake => {
+                "Checks for words that appear neither in the dictionary nor in `allowed-words`."
+            }
+            Rule::RepeatedWord => {
+                "Checks for a word repeated immediately after itself, separated only by whitespace."
+            }
+            Rule::ForbiddenWord => {
+                "Checks for words and phrases that are banned by `forbidden-words`, even if they \
+                 are spelled correctly."
+            }
+            Rule::DialectMismatch => {
+                "Checks for British or American spellings which differ from the configured \
+                 `dialect`, or from the rest of the file."
+            }
+        }
+    }
+
+    /// Returns whether or not the rule is checked when `select` is not configured. AS004 is
+    /// instead enabled by setting `dialect`, since there is no dialect to expect until then.
+    pub fn default_enabled(&self) -> bool {
+        match self {
+            Rule::SpellingMistake => true,
+            Rule::RepeatedWord => true,
+            Rule::ForbiddenWord => true,
+            Rule::DialectMismatch => false,
+        }
+    }
+
+    /// Returns the full documentation of the rule, as displayed by `--explain`.
+    pub fn documentation(&self) -> &'static str {
+        match self {
+            Rule::SpellingMistake => {
+                "## What it does
+Checks for words that appear neither in the dictionary nor in `allowed-words`.
+
+Tokens are split into words at camelCase, snake_case and kebab-case boundaries, and each word
+shorter than `min-word-length` is skipped.
+
+## Why is this bad?
+Spelling mistakes make documentation and code harder to read and search.
+
+## Example
+```text
+Recieve the mesage.
+```
+
+Use instead:
+```text
+Receive the message.
+```
+
+## Fix
+A fix is available when there is a single unambiguous correction."
+            }
+            Rule::RepeatedWord => {
+                "## What it does
+Checks for a word repeated immediately after itself, ignoring case, such as \"the the\". The two
+occurrences may be separated by whitespace, including line breaks, but not by punctuation.
+
+## Why is this bad?
+Doubled words are a common typo which a dictionary alone can never catch, since both words are
+spelled correctly.
+
+## Example
+```text
+Fetch the the cat.
+```
+
+Use instead:
+```text
+Fetch the cat.
+```
+
+## Fix
+The fix removes the second occurrence, along with the whitespace before it."
+            }
+            Rule::ForbiddenWord => {
+                "## What it does
+Checks for words and phrases that are listed in the `[forbidden-words]` table, even if they are
+spelled correctly.
+
+## Why is this bad?
+Style guides may ban words such as deprecated product names, or suggest plainer alternatives.
+
+## Example
+```toml
+[forbidden-words]
+utilize = \"use\"
+whitelist = { replacement = \"allowlist\", message = \"See the style guide.\" }
+```
+
+## Fix
+A fix is available when the word or phrase has a `replacement`."
+            }
+            Rule::DialectMismatch => {
+                "## What it does
+Checks for British or American spellings, such as `colour` and `color`, which differ from the
+configured `dialect`. With `dialect = \"consistent\"`, spellings are instead checked against the
+first such spelling in the same file.
+
+This rule is enabled by setting `dialect`.
+
+## Why is this bad?
+Mixing dialects within a project looks careless, and makes text harder to search.
+
+## Example
+```toml
+dialect = \"en-GB\"
+```
+
+```text
+The color of the center.
+```
+
+Use instead:
+```text
+The colour of the centre.
+```
+
+## Fix
+The fix replaces the word with its spelling in the expected dialect."
+            }
+        }
+    }
+
+    /// Returns every rule which is checked, given the rule selection.
+    ///
+    /// * `select` - The rules which are checked, if not the default rules.
+    /// * `extend_select` - Further rules which are checked.
+    /// * `ignore` - The rules which are not checked, taking precedence over any other selection.
+    /// * `has_dialect` - Whether `dialect` is set, which adds AS004 to the default rules.
+    pub fn enabled(
+        select: Option<&[Rule]>,
+        extend_select: &[Rule],
+        ignore: &[Rule],
+        has_dialect: bool,
+    ) -> Vec<Rule> {
+        let mut enabled: Vec<Rule> = Rule::ALL
+            .into_iter()
+            .filter(|rule| match select {
+                Some(selected) => selected.contains(rule),
+                None => rule.default_enabled() || (has_dialect && *rule == Rule::DialectMismatch),
+            })
+            .chain(extend_select.iter().copied())
+            .filter(|rule| !ignore.contains(rule))
+            .collect();
+        enabled.sort();
+        enabled.dedup();
+        enabled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use toml::Table;
+
+    use super::*;
+    use crate::config::config::load_config;
+    use crate::config::config::Configuration;
+
+    /// Checks `Rule::from_selector` matches every rule, a single rule, or rules sharing a prefix.
+    #[test]
+    fn from_selector_prefixes() {
+        assert_eq!(Rule::from_selector("ALL"), Rule::ALL.to_vec());
+        assert_eq!(Rule::from_selector("AS002"), vec![Rule::RepeatedWord]);
+        assert_eq!(Rule::from_selector("AS00").len(), Rule::ALL.len());
+        assert!(Rule::from_selector("AS9").is_empty());
+    }
+
+    /// Checks `Rule::from_selector` matches no rules for selectors which are not code prefixes,
+    /// rather than every rule.
+    #[test]
+    fn from_selector_invalid() {
+        for selector in ["", "A", "as001", "AS001,AS002", "ASX", " AS001"] {
+            assert!(Rule::from_selector(selector).is_empty(), "{}", selector);
+        }
+    }
+
+    /// Checks `Rule::enabled` applies `select`, then `extend-select`, then `ignore`.
+    #[test]
+    fn enabled_selection() {
+        assert_eq!(
+            Rule::enabled(None, &[], &[], false),
+            vec![
+                Rule::SpellingMistake,
+                Rule::RepeatedWord,
+                Rule::ForbiddenWord
+            ]
+        );
+        assert_eq!(
+            Rule::enabled(
+                Some(&[Rule::SpellingMistake, Rule::ForbiddenWord]),
+                &[Rule::RepeatedWord],
+                &[Rule::ForbiddenWord],
+                false
+            ),
+            vec![Rule::SpellingMistake, Rule::RepeatedWord]
+        );
+    }
+
+    /// Checks `Rule::enabled` only adds AS004 to the default rules when `dialect` is set, so that
+    /// selecting rules explicitly leaves it disabled.
+    #[test]
+    fn enabled_dialect() {
+        let enabled = |config: &str| {
+            let mut configuration = Configuration::default();
+            load_config(&config.parse::<Table>().unwrap(), &mut configuration).unwrap();
+            Rule::enabled(
+                configuration.select.as_deref(),
+                &configuration.extend_select,
+                &configuration.ignore,
+                configuration.dialect.is_some(),
+            )
+        };
+        assert!(enabled("dialect = \"en-GB\"\n").contains(&Rule::DialectMismatch));
+        assert_eq!(
+            enabled("select = [\"AS001\"]\ndialect = \"en-GB\"\n"),
+            vec![Rule::SpellingMistake]
+        );
+        assert_eq!(
+            enabled("select = [\"AS001\"]\nextend-select = [\"AS004\"]\ndialect = \"en-GB\"\n"),
+            vec![Rule::SpellingMistake, Rule::DialectMismatch]
+        );
+    }
+}
@@ -0,0 +1,297 @@
+use std::collections::HashMap;
+
+use regex::Regex;
+
+use crate::config::config::SkipSettings;
+
+/// Matches URLs with a scheme (e.g. `https://...`), or beginning with `www.`.
+const URL_PATTERN: &str = r#"(?i)\b(?:[a-z][a-z0-9+.-]*://|www\.)[^\s<>"'`]+"#;
+
+/// Matches email addresses.
+const EMAIL_PATTERN: &str = r"\b[\w.+-]+@[\w-]+(?:\.[\w-]+)+\b";
+
+/// Matches absolute Unix paths with at least two components (e.g. `/usr/bin`), paths relative to
+/// the home or current directory (e.g. `~/.config`), and relative paths to a file with an
+/// extension (e.g. `src/lib.rs`). The character preceding the path is matched as well, since
+/// `and/or` is not a path.
+const UNIX_PATH_PATTERN: &str = r#"(?m)(?:^|[\s(\["'=:,])(?:(?:~|\.{1,2})?(?:/[\w.@%+~-]+){2,}/?|(?:~|\.{1,2})/[\w.@%+~-]*|[\w.-]+(?:/[\w.@%+~-]+)+\.\w+)"#;
+
+/// Matches Windows paths with a drive letter (e.g. `C:\Windows`), and UNC paths.
+const WINDOWS_PATH_PATTERN: &str = r#"\b[A-Za-z]:\\[^\s"'<>|]*|\\\\[\w.$-]+(?:\\[^\s\\"'<>|]+)+"#;
+
+/// Matches hostnames ending with a common top-level domain (e.g. `docs.python.org`). Other
+/// top-level domains are not recognized, since e.g. `main.py` is more likely to be a file name.
+const HOSTNAME_PATTERN: &str = r"(?i)\b(?:[a-z0-9](?:[a-z0-9-]*[a-z0-9])?\.)+(?:com|org|net|edu|gov|io|dev|app|ai|co|uk|us|de|fr|jp|eu|info|me|xyz|cloud|local|localhost|internal)\b";
+
+/// Matches UUIDs.
+const UUID_PATTERN: &str =
+    r"\b[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}\b";
+
+/// Matches hexadecimal runs, such as git SHAs and other hashes, along with prefixed hexadecimal
+/// numbers and colors (e.g. `0x1f` and `#ff00aa`).
+const HEX_PATTERN: &str = r"(?:#|\b0[xX])[0-9a-fA-F]+\b|\b[0-9a-fA-F]{7,}\b";
+
+/// Matches JSON Web Tokens, whose header and payload are base64-encoded JSON objects.
+const JWT_PATTERN: &str = r"\beyJ[\w-]+\.eyJ[\w-]+\.[\w-]*";
+
+/// Matches runs of characters which could be base64-encoded (including the URL-safe variant).
+const BASE64_PATTERN: &str = r"[A-Za-z0-9+/_-]{20,}={0,2}";
+
+/// The length from which a run of base64 characters without `=` padding may be considered encoded
+/// data. Shorter runs are only skipped if padded, since long identifiers can otherwise resemble
+/// encoded data (e.g. `convertUtf8ToBase64ForHttp2Header`).
+const BASE64_MIN_UNPADDED_LENGTH: usize = 40;
+
+/// The Shannon entropy (in bits per character) above which a run of base64 characters is considered
+/// random, as a proportion of the highest entropy possible for its length.
+const BASE64_ENTROPY_RATIO: f64 = 0.8;
+
+/// Returns the Shannon entropy of a piece of text, in bits per character.
+///
+/// * `text` - The text being measured.
+fn entropy(text: &str) -> f64 {
+    let mut counts: HashMap<char, usize> = HashMap::new();
+    let mut length = 0;
+    for character in text.chars() {
+        *counts.entry(character).or_default() += 1;
+        length += 1;
+    }
+    counts
+        .values()
+        .map(|count| {
+            let probability = *count as f64 / length as f64;
+            -probability * probability.log2()
+        })
+        .sum()
+}
+
+/// Returns whether or not a hexadecimal run is a hash, number or color, rather than a word which
+/// happens to consist only of the letters `a` to `f` (e.g. `defaced` or `#decade`). Only runs
+/// containing a digit are treated as hexadecimal, as the `0x` prefix of a number already is.
+///
+/// * `text` - The hexadecimal run.
+fn is_hash(text: &str) -> bool {
+    text.chars().any(|c| c.is_ascii_digit())
+}
+
+/// Returns whether or not a run of base64 characters looks like encoded data, rather than a long
+/// identifier (e.g. `getHTTPResponseCode2`). Encoded data is either padded with `=` or long, mixes
+/// uppercase letters, lowercase letters and several digits (or the symbols `+`, `/` and `=`), and
+/// has a high entropy for its length.
+///
+/// * `text` - The run of base64 characters.
+fn is_base64(text: &str) -> bool {
+    let has_uppercase = text.chars().any(|c| c.is_ascii_uppercase());
+    let has_lowercase = text.chars().any(|c| c.is_ascii_lowercase());
+    let digits = text.chars().filter(char::is_ascii_digit).count();
+    let has_symbol = text.contains(['+', '/', '=']);
+    let highest_entropy = (text.len().min(64) as f64).log2();
+    (text.ends_with('=') || text.len() >= BASE64_MIN_UNPADDED_LENGTH)
+        && has_uppercase
+        && has_lowercase
+        && (digits >= 2 || (digits >= 1 && has_symbol))
+        && entropy(text) >= BASE64_ENTROPY_RATIO * highest_entropy
+}
+
+/// A check which a match of a pattern must pass for it to be skipped.
+type SkipCheck = fn(&str) -> bool;
+
+/// The patterns matching text which is skipped before a file is split into words.
+pub struct SkipPatterns {
+    /// Every enabled pattern, along with a check which each match must pass to be skipped.
+    regexes: Vec<(Regex, Option<SkipCheck>)>,
+}
+
+impl SkipPatterns {
+    /// Compiles every pattern enabled in the configuration.
+    ///
+    /// * `settings` - Which kinds of text are skipped.
+    /// * `ignore_regex` - The patterns from the `ignore-regex` setting.
+    pub fn new(settings: &SkipSettings, ignore_regex: &[Regex]) -> SkipPatterns {
+        let mut patterns: Vec<(&str, Option<SkipCheck>)> = Vec::new();
+        if settings.urls {
+            patterns.push((URL_PATTERN, None));
+        }
+        if settings.emails {
+            patterns.push((EMAIL_PATTERN, None));
+        }
+        if settings.paths {
+            patterns.push((UNIX_PATH_PATTERN, None));
+            patterns.push((WINDOWS_PATH_PATTERN, None));
+        }
+        if settings.hostnames {
+            patterns.push((HOSTNAME_PATTERN, None));
+        }
+        if settings.hashes {
+            patterns.push((UUID_PATTERN, None));
+            patterns.push((HEX_PATTERN, Some(is_hash)));
+        }
+        if settings.base64 {
+            patterns.push((JWT_PATTERN, None));
+            patterns.push((BASE64_PATTERN, Some(is_base64)));
+        }
+
+        // The built-in patterns are known to be valid.
+        let mut regexes: Vec<(Regex, Option<SkipCheck>)> = patterns
+            .into_iter()
+            .map(|(pattern, check)| (Regex::new(pattern).unwrap(), check))
+            .collect();
+        regexes.extend(ignore_regex.iter().map(|regex| (regex.clone(), None)));
+        SkipPatterns { regexes }
+    }
+
+    /// Returns a piece of text with every character matched by a pattern replaced by a space,
+    /// except for newlines, so that every remaining character keeps its position.
+    ///
+    /// * `text` - The text being checked.
+    pub fn mask(&self, text: &str) -> String {
+        let mut skipped: Vec<(usize, usize)> = Vec::new();
+        for (regex, check) in &self.regexes {
+            skipped.extend(
+                regex
+                    .find_iter(text)
+                    .filter(|found| check.is_none_or(|check| check(found.as_str())))
+                    .map(|found| (found.start(), found.end())),
+            );
+        }
+        if skipped.is_empty() {
+            return text.to_owned();
+        }
+        skipped.sort();
+
+        let mut masked = String::with_capacity(text.len());
+        let mut next_skipped = skipped.iter().peekable();
+        for (offset, character) in text.char_indices() {
+            while next_skipped.peek().is_some_and(|(_, end)| *end <= offset) {
+                next_skipped.next();
+            }
+            let is_skipped = next_skipped
+                .clone()
+                .take_while(|(start, _)| *start <= offset)
+                .any(|(_, end)| offset < *end);
+            if is_skipped && character != '\n' {
+                masked.push(' ');
+            } else {
+                masked.push(character);
+            }
+        }
+        masked
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the words remaining once every pattern is skipped.
+    ///
+    /// * `text` - The text being checked.
+    fn remaining_words(text: &str) -> Vec<String> {
+        let skip_patterns = SkipPatterns::new(
+            &SkipSettings {
+                ..Default::default()
+            },
+            &[],
+        );
+        skip_patterns
+            .mask(text)
+            .split_whitespace()
+            .map(|word| word.to_owned())
+            .collect()
+    }
+
+    /// Checks `mask` skips URLs, email addresses and hostnames, keeping the surrounding words.
+    #[test]
+    fn mask_urls_emails_hostnames() {
+        assert_eq!(
+            remaining_words(
+                "See https://github.com/clockback/antiseptic, mail someone@example.org or visit docs.python.org."
+            ),
+            vec!["See", "mail", "or", "visit", "."]
+        );
+    }
+
+    /// Checks `mask` skips Unix and Windows paths, but not words separated by a slash.
+    #[test]
+    fn mask_paths() {
+        assert_eq!(
+            remaining_words(
+                "Run /usr/local/bin/tool or C:\\Tools\\tool.exe on src/lib.rs and/or ~/.config"
+            ),
+            vec!["Run", "or", "on", "and/or"]
+        );
+    }
+
+    /// Checks `mask` skips hashes, UUIDs and colors, but not words made of hexadecimal letters.
+    #[test]
+    fn mask_hashes() {
+        assert_eq!(
+            remaining_words(
+                "commit 3f2a9bc0 id 123e4567-e89b-12d3-a456-426614174000 color #ffaa00 was defaced"
+            ),
+            vec!["commit", "id", "color", "was", "defaced"]
+        );
+        assert_eq!(
+            remaining_words("a #deface or #decade at 0xc0ffee"),
+            vec!["a", "#deface", "or", "#decade", "at"]
+        );
+    }
+
+    /// Checks `mask` skips base64 blobs and JSON Web Tokens, but not long identifiers.
+    #[test]
+    fn mask_base64() {
+        assert_eq!(
+            remaining_words(
+                "integrity sha512-Xk3vQ9+pLm2ZrT8yWb4NcD7eFh1jKs0A== token eyJhbGciOiJIUzI1NiJ9.eyJzdWIiOiIxIn0.sig getHTTPResponseCode2"
+            ),
+            vec!["integrity", "token", "getHTTPResponseCode2"]
+        );
+        assert_eq!(
+            remaining_words(
+                "call convertUtf8ToBase64ForHttp2Header or parseUtf8ToBase64AndWriteToHttp2Stream \
+                 with dGhlIHF1aWNrIGJyb3duIGZveCBqdW1wcyBvdmVyIDEz"
+            ),
+            vec![
+                "call",
+                "convertUtf8ToBase64ForHttp2Header",
+                "or",
+                "parseUtf8ToBase64AndWriteToHttp2Stream",
+                "with"
+            ]
+        );
+    }
+
+    /// Checks `mask` skips matches of the `ignore-regex` patterns.
+    #[test]
+    fn mask_ignore_regex() {
+        let ignore_regex = vec![
+            Regex::new(r"\b[A-Z]+-\d+\b").unwrap(),
+            Regex::new(r"\{\{.*?\}\}").unwrap(),
+        ];
+        let skip_patterns = SkipPatterns::new(
+            &SkipSettings {
+                ..Default::default()
+            },
+            &ignore_regex,
+        );
+        assert_eq!(
+            skip_patterns.mask("Fixes ABCD-1234 for {{ foo_bar }}."),
+            "Fixes           for              ."
+        );
+    }
+
+    /// Checks `mask` keeps the position of every remaining character, including multi-byte ones.
+    #[test]
+    fn mask_keeps_positions() {
+        let skip_patterns = SkipPatterns::new(
+            &SkipSettings {
+                ..Default::default()
+            },
+            &[],
+        );
+        assert_eq!(
+            skip_patterns.mask("é https://x.io/é\nmot"),
+            "é               \nmot"
+        );
+    }
+}
@@ -1,28 +1,25 @@
 use std::borrow::{Borrow, BorrowMut};
 use std::collections::HashSet;
 use std::fs::File;
-use std::io::{self, BufRead};
+use std::io::{self, BufRead, Read};
 use std::path::Path;
 use std::path::PathBuf;
 
 use colored::Colorize;
-use utf8_chars::BufReadCharsExt;
 
+use crate::config::config::Configuration;
+use crate::config::config::DigitMode;
+use crate::config::config::ForbiddenWord;
+use crate::diagnostic::Diagnostic;
+use crate::diagnostic::ReadPosition;
+use crate::dialect::DialectChecker;
+use crate::dialect::DialectVariants;
+use crate::directives::parse_line;
 use crate::errors::all_errors::AntisepticError;
-
-/// The position of an identified token. This is primarily used in error output for the user to
-/// locate where an error has happened.
-struct ReadPosition {
-    /// The file which Antiseptic is checking.
-    file: PathBuf,
-
-    /// The line number of the file in which Antiseptic is checking. This follows 1-based indexing.
-    line_no: u64,
-
-    /// The index of the first character for the token where Antiseptic is checking. The first
-    /// character in a line is 1.
-    char_no: u64,
-}
+use crate::fix::match_case;
+use crate::languages::language::Language;
+use crate::rules::Rule;
+use crate::skip::SkipPatterns;
 
 /// Reads another word in the dictionary into a buffer.
 ///
@@ -35,7 +32,7 @@ fn read_word(
     let result = match bufreader.read_until(b'\n', buffer) {
         Ok(word) => word,
         Err(_e) => {
-            println!("{}", "Failed to read text in dictionary.".red());
+            eprintln!("{}", "Failed to read text in dictionary.".red());
             return Err(AntisepticError::ReadingDictionaryFailed);
         }
     };
@@ -56,7 +53,7 @@ pub fn get_word_characters(src: &Path) -> Result<HashSet<char>, AntisepticError>
     let open_dict = match File::open(path_buf) {
         Ok(result) => result,
         Err(_e) => {
-            println!("{}", "Error while reading dictionary.".red());
+            eprintln!("{}", "Error while reading dictionary.".red());
             return Err(AntisepticError::InvalidDictionaryPath);
         }
     };
@@ -98,13 +95,21 @@ pub fn get_word_set(src: &Path) -> Result<HashSet<String>, AntisepticError> {
     path_buf.push("assets");
     path_buf.push("dictionaries");
     path_buf.push("en.txt");
-    let full_path = path_buf.to_str().unwrap();
+
+    get_word_set_from_file(&path_buf)
+}
+
+/// Finds all words in a dictionary file, which lists one word per line.
+///
+/// * `path` - The path to the dictionary file.
+pub fn get_word_set_from_file(path: &Path) -> Result<HashSet<String>, AntisepticError> {
+    let full_path = path.to_string_lossy();
 
     // Attempts reading the file.
-    let open_dict = match File::open(full_path) {
+    let open_dict = match File::open(path) {
         Ok(result) => result,
         Err(_e) => {
-            println!(
+            eprintln!(
                 "{}{}{}",
                 "Error while reading dictionary.".red(),
                 full_path.red(),
@@ -115,7 +120,7 @@ pub fn get_word_set(src: &Path) -> Result<HashSet<String>, AntisepticError> {
     };
 
     // Obtains an iterator for each word (without whitespace) in the dictionary.
-    let iter_lines = io::BufReader::new(open_dict).lines().flatten();
+    let iter_lines = io::BufReader::new(open_dict).lines().map_while(Result::ok);
 
     // Transforms the iterator's values into a set.
     Ok(iter_lines.collect())
@@ -123,123 +128,256 @@ pub fn get_word_set(src: &Path) -> Result<HashSet<String>, AntisepticError> {
 
 /// Returns whether or not a word appears in the dictionary.
 ///
-/// Also includes printing an error message in the event the word is absent.
+/// Also includes recording a diagnostic in the event the word is absent.
 ///
 /// * `read_position` - The position of the token for the word.
 /// * `word` - The word being checked for spelling mistakes.
+/// * `token` - The token from which the word was taken.
 /// * `words_allowed` - The set of words which are considered correct.
+/// * `min_word_length` - The number of characters a word needs in order to be checked.
+/// * `diagnostics` - The list of diagnostics to be populated.
 fn word_is_incorrect(
     read_position: &ReadPosition,
-    word: &String,
+    word: &str,
+    token: &str,
     words_allowed: &HashSet<String>,
+    min_word_length: usize,
+    diagnostics: &mut Vec<Diagnostic>,
 ) -> bool {
     let lower_word = word.to_lowercase();
-    if word.len() > 3 && !words_allowed.contains(&lower_word) {
-        println!(
-            "{}{}{}{}{}{} {} spelling mistake `{}`",
-            read_position.file.to_string_lossy().bold(),
-            ":".cyan(),
-            read_position.line_no,
-            ":".cyan(),
-            read_position.char_no,
-            ":".cyan(),
-            "AS001".red().bold(),
-            word
-        );
+    if word.chars().count() >= min_word_length && !words_allowed.contains(&lower_word) {
+        diagnostics.push(Diagnostic::new(
+            read_position.clone(),
+            Rule::SpellingMistake,
+            word,
+            token,
+        ));
         return true;
     }
-    return false;
+    false
+}
+
+/// A token which has been read recently, used to find repeated words and forbidden phrases.
+struct RecentToken {
+    /// The token, in lowercase.
+    lowercase: String,
+
+    /// The position of the token.
+    position: ReadPosition,
+
+    /// The byte offset of the token within the file's contents.
+    offset: usize,
+}
+
+/// Records a diagnostic for every forbidden word or phrase ending with the token just read.
+///
+/// * `recent_tokens` - The most recent tokens, separated from one another only by whitespace, the
+///   last of which is the token just read.
+/// * `end_position` - The position following the last character of the token just read.
+/// * `end_offset` - The byte offset following the last character of the token just read.
+/// * `contents` - The contents of the file.
+/// * `forbidden_words` - The words and phrases which are banned.
+/// * `diagnostics` - The list of diagnostics to be populated.
+fn find_forbidden_words(
+    recent_tokens: &[RecentToken],
+    end_position: &ReadPosition,
+    end_offset: usize,
+    contents: &str,
+    forbidden_words: &[ForbiddenWord],
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    for forbidden_word in forbidden_words {
+        let length = forbidden_word.words.len();
+        if length > recent_tokens.len() {
+            continue;
+        }
+        let phrase = &recent_tokens[recent_tokens.len() - length..];
+        let is_match = phrase
+            .iter()
+            .zip(&forbidden_word.words)
+            .all(|(recent_token, word)| recent_token.lowercase == *word);
+        if !is_match {
+            continue;
+        }
+
+        // The phrase is reported as it appears in the file, which may span multiple lines.
+        let text = &contents[phrase[0].offset..end_offset];
+        let replacement = forbidden_word
+            .replacement
+            .as_ref()
+            .map(|replacement| match_case(text, replacement));
+        diagnostics.push(Diagnostic {
+            end_line_no: end_position.line_no,
+            end_char_no: end_position.char_no,
+            end_byte_no: end_position.byte_no,
+            end_utf16_no: end_position.utf16_no,
+            note: forbidden_word.message.clone(),
+            suggestions: replacement.iter().cloned().collect(),
+            fix: replacement,
+            ..Diagnostic::new(phrase[0].position.clone(), Rule::ForbiddenWord, text, text)
+        });
+    }
+}
+
+/// Records a diagnostic for a token which repeats the token before it, e.g. the second `the` in
+/// "the the". Its fix removes the token along with the whitespace preceding it.
+///
+/// * `read_position` - The position of the repeated token.
+/// * `token` - The repeated token.
+/// * `diagnostics` - The list of diagnostics to be populated.
+fn record_repeated_word(
+    read_position: &ReadPosition,
+    token: &str,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    diagnostics.push(Diagnostic {
+        fix: Some(String::new()),
+        ..Diagnostic::new(read_position.clone(), Rule::RepeatedWord, token, token)
+    });
+}
+
+/// Splits a piece of a token, containing no separators, into words at camelCase boundaries.
+///
+/// A new word begins at an uppercase letter following a lowercase letter or digit (e.g. `leftRight`
+/// or `ipv6Address`), and at the last uppercase letter of an acronym followed by a lowercase letter
+/// (e.g. `ABCMethod`).
+///
+/// * `characters` - The characters of the piece.
+/// * `offset` - The number of characters in the token preceding the piece.
+/// * `words` - The list of words, along with their offsets within the token, to be populated.
+fn split_camel_case(characters: &[char], offset: usize, words: &mut Vec<(usize, String)>) {
+    let mut word_start = 0;
+    for index in 1..characters.len() {
+        let character = characters[index];
+        let previous = characters[index - 1];
+        let next_is_lowercase = characters
+            .get(index + 1)
+            .is_some_and(|next| next.is_lowercase());
+        let is_boundary = character.is_uppercase()
+            && (previous.is_lowercase()
+                || previous.is_ascii_digit()
+                || (previous.is_uppercase() && next_is_lowercase));
+        if is_boundary {
+            words.push((
+                offset + word_start,
+                characters[word_start..index].iter().collect(),
+            ));
+            word_start = index;
+        }
+    }
+    if word_start < characters.len() {
+        words.push((
+            offset + word_start,
+            characters[word_start..].iter().collect(),
+        ));
+    }
+}
+
+/// Splits a token into the words it contains, along with the number of characters in the token
+/// preceding each word.
+///
+/// The following boundaries separate words:
+///
+/// * Underscores, as in `snake_case` and `SCREAMING_SNAKE`.
+/// * Hyphens, as in `kebab-case`.
+/// * Digits, as in `word2vec`, unless `split_digits` is false, in which case they belong to the
+///   word they appear in.
+/// * camelCase boundaries, as in `leftRight` and `ABCMethod`.
+///
+/// * `token` - The token being split.
+/// * `split_digits` - Whether or not digits separate words.
+fn split_token(token: &str, split_digits: bool) -> Vec<(usize, String)> {
+    let mut words: Vec<(usize, String)> = Vec::new();
+    let mut piece: Vec<char> = Vec::new();
+    let mut piece_start = 0;
+    for (index, character) in token.chars().enumerate() {
+        let is_separator =
+            character == '_' || character == '-' || (split_digits && character.is_ascii_digit());
+        if is_separator {
+            split_camel_case(&piece, piece_start, &mut words);
+            piece.clear();
+            piece_start = index + 1;
+        } else {
+            piece.push(character);
+        }
+    }
+    split_camel_case(&piece, piece_start, &mut words);
+    words
 }
 
 /// Returns whether or not each of the token's words appears in the dictionary.
 ///
 /// For example, the token ABCMethod contains the words "ABC" and "Method". A single token may
-/// therefore contain multiple spelling mistakes.
+/// therefore contain multiple spelling mistakes, each of which is reported at the column of the
+/// word rather than that of the token.
 ///
 /// * `read_position` - The position of the token.
 /// * `token` - The token being checked for spelling mistakes.
 /// * `words_allowed` - The set of words which are considered correct.
+/// * `min_word_length` - The number of characters a word needs in order to be checked.
+/// * `split_digits` - Whether or not digits separate words.
+/// * `dialect_checker` - The checker for spellings belonging to an unexpected dialect, which
+///   takes over from the dictionary for any word spelled differently between dialects.
+/// * `diagnostics` - The list of diagnostics to be populated.
 fn process_token(
     read_position: &ReadPosition,
-    token: &String,
+    token: &str,
     words_allowed: &HashSet<String>,
+    min_word_length: usize,
+    split_digits: bool,
+    dialect_checker: &mut DialectChecker,
+    diagnostics: &mut Vec<Diagnostic>,
 ) -> bool {
-    let mut word = String::new();
-    let mut uppercase_triggers_new_word = false;
-    let mut is_acronym = false;
     let mut found_mistake = false;
-
-    // Iterates over every character in the token.
-    for character in token.chars() {
-        let length_so_far = word.len();
-        let is_uppercase = character.is_uppercase();
-
-        // Considers if exactly one character has already been fetched prior. When two characters
-        // are loaded, it can be determined whether the word is all lower-case (e.g. cake),
-        // capitalized (e.g. Cake), or all-caps (e.g. CAKE).
-        if length_so_far == 1 {
-            let mut chars = word.chars();
-            let first = chars.next().unwrap();
-
-            // If there is only one lowercase character, followed by an uppercase character, the
-            // first character is its own word.
-            if first.is_lowercase() && is_uppercase {
-                found_mistake =
-                    found_mistake | word_is_incorrect(read_position, word.borrow(), words_allowed);
-                word.remove(0);
-            }
-            // In any other case, the two letters belong to either an acronym/all-caps word, or a
-            // word that is either capitalized or lower-case.
-            else if is_uppercase {
-                is_acronym = true;
-            } else {
-                uppercase_triggers_new_word = true;
-            }
-        }
-        // In the event it is already known what kind of word exists, checks if the word is being
-        // terminated.
-        else if length_so_far > 1 {
-            if uppercase_triggers_new_word && is_uppercase {
-                found_mistake =
-                    found_mistake | word_is_incorrect(read_position, word.borrow(), words_allowed);
-                word.clear();
-                uppercase_triggers_new_word = false;
-            } else if is_acronym && !is_uppercase {
-                let previous_character = word.pop().unwrap();
-                found_mistake =
-                    found_mistake | word_is_incorrect(read_position, word.borrow(), words_allowed);
-                word.clear();
-                word.push(previous_character);
-                is_acronym = false;
-            }
+    for (word_offset, word) in split_token(token, split_digits) {
+        let preceding: String = token.chars().take(word_offset).collect();
+        let word_position = ReadPosition {
+            char_no: read_position.char_no + word_offset as u64,
+            byte_no: read_position.byte_no + preceding.len() as u64,
+            utf16_no: read_position.utf16_no + preceding.encode_utf16().count() as u64,
+            ..read_position.clone()
+        };
+        if dialect_checker.check(&word_position, &word, token, diagnostics) {
+            continue;
         }
-        word.push(character);
-    }
-
-    // If the end of the token is found, processes the final word.
-    if !word.is_empty() {
-        found_mistake =
-            found_mistake | word_is_incorrect(read_position, word.borrow(), words_allowed);
+        found_mistake |= word_is_incorrect(
+            &word_position,
+            &word,
+            token,
+            words_allowed,
+            min_word_length,
+            diagnostics,
+        );
     }
-
-    return found_mistake;
+    found_mistake
 }
 
 /// Checks for spelling mistakes in a file.
 ///
 /// * `file` - The path to the file being checked for spelling mistakes.
+/// * `language` - The language of the file, which determines which parts of it are checked.
+/// * `configuration` - The settings loaded from the configuration file.
+/// * `skip_patterns` - The patterns matching text which is not checked, such as URLs.
 /// * `characters_allowed` - Every character that can be considered part of a word.
 /// * `words_allowed` - The set of words which are considered correct.
+/// * `dialect_variants` - The words which are spelled differently in British and American English.
+/// * `diagnostics` - The list of diagnostics to be populated.
+#[allow(clippy::too_many_arguments)]
 pub fn read_file(
     file: &PathBuf,
+    language: &Language,
+    configuration: &Configuration,
+    skip_patterns: &SkipPatterns,
     characters_allowed: &HashSet<char>,
     words_allowed: &HashSet<String>,
+    dialect_variants: &DialectVariants,
+    diagnostics: &mut Vec<Diagnostic>,
 ) -> Result<(), AntisepticError> {
     // Attempts reading the file.
-    let open_file = match File::open(file) {
+    let mut open_file = match File::open(file) {
         Ok(result) => result,
         Err(_e) => {
-            println!(
+            eprintln!(
                 "{}{}{}",
                 "File ".red(),
                 file.to_string_lossy().red(),
@@ -249,58 +387,211 @@ pub fn read_file(
         }
     };
 
-    // Attempts to read through the file character by character.
-    let mut bufreader = io::BufReader::new(open_file);
-    let char_iter = bufreader.chars();
+    // Reads the whole file, checking that it is valid UTF-8. Although characters are processed one
+    // at a time below, they cannot be streamed from the file: which characters are checked depends
+    // on constructs that may span many lines (e.g. docstrings, block comments and fenced code
+    // blocks), as do patterns from `ignore-regex`, and fixes to forbidden phrases are taken from
+    // the original text.
+    let mut contents = String::new();
+    match open_file.read_to_string(&mut contents) {
+        Ok(_result) => (),
+        Err(err) if err.kind() == io::ErrorKind::InvalidData => {
+            return Err(AntisepticError::CheckedFileIsNotUTF8);
+        }
+        Err(_err) => {
+            eprintln!(
+                "{}{}",
+                "Issue occurred while reading utf8 characters from ".red(),
+                file.to_string_lossy().red()
+            );
+            return Err(AntisepticError::IssueReadingFile);
+        }
+    }
 
-    let mut token = String::new();
-    let mut token_invalid = false;
+    // Only the parts of the file relevant to its language are checked, while directives are only
+    // recognized in its comments. Text such as URLs is skipped before the file is split into words.
+    let (checked_text, comment_text) = language.checked_text(&contents, configuration);
+    let checked_text = skip_patterns.mask(&checked_text);
 
+    let mut dialect_checker = DialectChecker::new(dialect_variants, configuration.dialect);
+
+    let mut token = String::new();
+    let mut token_start: u64 = 0;
+    let mut token_start_byte: u64 = 0;
+    let mut token_start_utf16: u64 = 0;
+
+    let mut token_start_offset: usize = 0;
+
+    // The most recent tokens, as long as only whitespace separates them, are kept to find repeated
+    // words and forbidden phrases. Only as many are kept as the longest phrase has words.
+    let mut recent_tokens: Vec<RecentToken> = Vec::new();
+    let longest_phrase = configuration
+        .forbidden_words
+        .iter()
+        .map(|forbidden_word| forbidden_word.words.len())
+        .max()
+        .unwrap_or(0)
+        .max(1);
+
+    // The comments in the current line are kept so that any directives within them can be applied
+    // once it ends.
+    let mut line = String::new();
+    let file_start = diagnostics.len();
+    let mut line_start = diagnostics.len();
+    let mut checking_disabled = false;
+
+    // Besides characters, the line is measured in bytes and UTF-16 code units. These are counted
+    // from the original characters, since masking may change their length.
     let mut line_no = 1;
     let mut char_no: u64 = 0;
-
-    // Iterates over each character in the file.
-    for character_option in char_iter {
+    let mut line_bytes: u64 = 0;
+    let mut line_utf16: u64 = 0;
+    let mut offset: usize = 0;
+
+    // Iterates over each character in the file, followed by a final newline so that the last
+    // token and line are processed.
+    let original_characters = contents.chars().chain(['\n']);
+    let checked_characters = checked_text.chars().chain(['\n']);
+    let comment_characters = comment_text.chars().chain(['\n']);
+    for ((original_character, character), comment_character) in original_characters
+        .zip(checked_characters)
+        .zip(comment_characters)
+    {
         char_no += 1;
 
-        // Checks that the character is valid UTF-8.
-        let character = match character_option {
-            Ok(result) => result,
-            Err(_err) => {
-                println!(
-                    "{}{}",
-                    "Issue occurred while reading utf8 characters from ".red(),
-                    file.to_string_lossy().red()
-                );
-                return Err(AntisepticError::IssueReadingFile);
+        // If the character can belong to a word, adds it to a token. Tokens also include digits,
+        // underscores and hyphens, which are used to split them into words.
+        let is_word_character = character.is_alphabetic()
+            || characters_allowed.contains(&character)
+            || character.is_ascii_digit()
+            || character == '_'
+            || character == '-';
+        if is_word_character {
+            if token.is_empty() {
+                token_start = char_no;
+                token_start_byte = line_bytes + 1;
+                token_start_utf16 = line_utf16 + 1;
+                token_start_offset = offset;
             }
-        };
-
-        // If the character can belong to a word, adds it to a token.
-        if character.is_alphabetic() || characters_allowed.contains(&character) {
             token.push(character);
         }
         // If the character is whitespace/punctuation, and a token has already started to be formed,
-        // checks the token for spelling mistakes.
+        // checks the token for spelling mistakes, unless checking has been disabled.
         else if !token.is_empty() {
             let read_position = ReadPosition {
                 file: file.clone(),
                 line_no,
-                char_no: char_no - (token.len() as u64),
+                char_no: token_start,
+                byte_no: token_start_byte,
+                utf16_no: token_start_utf16,
             };
-            token_invalid =
-                token_invalid | process_token(&read_position, token.borrow(), words_allowed);
+            let is_skipped = configuration.digits == DigitMode::Skip
+                && token.chars().any(|c| c.is_ascii_digit());
+            if !checking_disabled && !is_skipped {
+                process_token(
+                    &read_position,
+                    token.borrow(),
+                    words_allowed,
+                    configuration.min_word_length,
+                    configuration.digits == DigitMode::Split,
+                    &mut dialect_checker,
+                    diagnostics,
+                );
+            }
+
+            // Only tokens containing letters are compared, so that e.g. "1 1" is not reported.
+            let lowercase = token.to_lowercase();
+            if token.chars().any(char::is_alphabetic) {
+                let is_repeated = recent_tokens
+                    .last()
+                    .is_some_and(|recent_token| recent_token.lowercase == lowercase);
+                if !checking_disabled && is_repeated {
+                    record_repeated_word(&read_position, token.borrow(), diagnostics);
+                }
+                if recent_tokens.len() == longest_phrase {
+                    recent_tokens.remove(0);
+                }
+                recent_tokens.push(RecentToken {
+                    lowercase,
+                    position: read_position,
+                    offset: token_start_offset,
+                });
+                if !checking_disabled {
+                    let end_position = ReadPosition {
+                        file: file.clone(),
+                        line_no,
+                        char_no,
+                        byte_no: line_bytes + 1,
+                        utf16_no: line_utf16 + 1,
+                    };
+                    find_forbidden_words(
+                        &recent_tokens,
+                        &end_position,
+                        offset,
+                        &contents,
+                        &configuration.forbidden_words,
+                        diagnostics,
+                    );
+                }
+            } else {
+                recent_tokens.clear();
+            }
             token.clear();
         }
 
-        // Tracks any new lines in the file to determine the reading position.
+        // Repeated words and the words of a phrase must be separated by whitespace alone. The
+        // original character is used, since skipped text (e.g. inline code) is masked by spaces.
+        if !is_word_character && !original_character.is_whitespace() {
+            recent_tokens.clear();
+        }
+        offset += original_character.len_utf8();
+
+        // Tracks any new lines in the file to determine the reading position, applying any
+        // directives in the line that has just ended.
         if character == '\n' {
+            let directives = parse_line(&line);
+            if directives.skip_file {
+                diagnostics.truncate(file_start);
+                return Ok(());
+            }
+
+            // Lines within a disabled region, including those containing the `off` and `on`
+            // directives, have all of their diagnostics discarded.
+            let was_disabled = checking_disabled;
+            if directives.disable {
+                checking_disabled = true;
+            } else if directives.enable {
+                checking_disabled = false;
+            }
+            if was_disabled || checking_disabled {
+                diagnostics.truncate(line_start);
+            } else {
+                directives.apply(diagnostics, line_start);
+            }
+
+            line.clear();
+            line_start = diagnostics.len();
             line_no += 1;
             char_no = 0;
+            line_bytes = 0;
+            line_utf16 = 0;
+        } else {
+            line.push(comment_character);
+            line_bytes += original_character.len_utf8() as u64;
+            line_utf16 += original_character.len_utf16() as u64;
         }
     }
 
-    if token_invalid {
+    if checking_disabled {
+        eprintln!(
+            "{}{}{}",
+            "WARNING: ".yellow(),
+            file.to_string_lossy().yellow(),
+            " ends while checking is disabled by `antiseptic: off`.".yellow()
+        );
+    }
+
+    if diagnostics.len() > file_start {
         return Err(AntisepticError::SpellingMistakeFound);
     }
 
@@ -310,6 +601,7 @@ pub fn read_file(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::diagnostic::ColumnMode;
 
     /// Checks `word_is_incorrect` returns false when word doesn't contain mistake.
     #[test]
@@ -319,12 +611,23 @@ mod tests {
             file: pathbuf,
             line_no: 1,
             char_no: 1,
+            byte_no: 1,
+            utf16_no: 1,
         };
         let word = "antiseptic".to_owned();
         let mut words_allowed: HashSet<String> = HashSet::new();
         words_allowed.insert("antiseptic".to_owned());
-        let incorrect = word_is_incorrect(&read_position, &word, &words_allowed);
+        let mut diagnostics: Vec<Diagnostic> = Vec::new();
+        let incorrect = word_is_incorrect(
+            &read_position,
+            &word,
+            &word,
+            &words_allowed,
+            4,
+            &mut diagnostics,
+        );
         assert!(!incorrect);
+        assert!(diagnostics.is_empty());
     }
 
     /// Checks `word_is_incorrect` returns true when word contains mistake.
@@ -335,12 +638,54 @@ mod tests {
             file: pathbuf,
             line_no: 1,
             char_no: 1,
+            byte_no: 1,
+            utf16_no: 1,
         };
         let word = "wrong".to_owned();
         let mut words_allowed: HashSet<String> = HashSet::new();
         words_allowed.insert("right".to_owned());
-        let incorrect = word_is_incorrect(&read_position, &word, &words_allowed);
+        let mut diagnostics: Vec<Diagnostic> = Vec::new();
+        let incorrect = word_is_incorrect(
+            &read_position,
+            &word,
+            &word,
+            &words_allowed,
+            4,
+            &mut diagnostics,
+        );
         assert!(incorrect);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].word, "wrong");
+    }
+
+    /// Checks `word_is_incorrect` counts the minimum word length in characters rather than bytes.
+    #[test]
+    fn word_is_incorrect_min_word_length() {
+        let read_position = ReadPosition {
+            file: PathBuf::new(),
+            line_no: 1,
+            char_no: 1,
+            byte_no: 1,
+            utf16_no: 1,
+        };
+        let words_allowed: HashSet<String> = HashSet::new();
+        let mut diagnostics: Vec<Diagnostic> = Vec::new();
+        assert!(!word_is_incorrect(
+            &read_position,
+            "çéà",
+            "çéà",
+            &words_allowed,
+            4,
+            &mut diagnostics,
+        ));
+        assert!(word_is_incorrect(
+            &read_position,
+            "çéà",
+            "çéà",
+            &words_allowed,
+            3,
+            &mut diagnostics,
+        ));
     }
 
     /// Checks `process_token` returns false when token doesn't contain mistake.
@@ -351,12 +696,23 @@ mod tests {
             file: pathbuf,
             line_no: 1,
             char_no: 1,
+            byte_no: 1,
+            utf16_no: 1,
         };
         let token = "leftRight".to_owned();
         let mut words_allowed: HashSet<String> = HashSet::new();
         words_allowed.insert("left".to_owned());
         words_allowed.insert("right".to_owned());
-        let incorrect = process_token(&read_position, &token, &words_allowed);
+        let mut diagnostics: Vec<Diagnostic> = Vec::new();
+        let incorrect = process_token(
+            &read_position,
+            &token,
+            &words_allowed,
+            4,
+            true,
+            &mut DialectChecker::new(&DialectVariants::default(), None),
+            &mut diagnostics,
+        );
         assert!(!incorrect);
     }
 
@@ -368,11 +724,265 @@ mod tests {
             file: pathbuf,
             line_no: 1,
             char_no: 1,
+            byte_no: 1,
+            utf16_no: 1,
         };
         let token = "leftRight".to_owned();
         let mut words_allowed: HashSet<String> = HashSet::new();
         words_allowed.insert("right".to_owned());
-        let incorrect = process_token(&read_position, &token, &words_allowed);
+        let mut diagnostics: Vec<Diagnostic> = Vec::new();
+        let incorrect = process_token(
+            &read_position,
+            &token,
+            &words_allowed,
+            4,
+            true,
+            &mut DialectChecker::new(&DialectVariants::default(), None),
+            &mut diagnostics,
+        );
+        assert!(incorrect);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].word, "left");
+        assert_eq!(diagnostics[0].token, "leftRight");
+        assert_eq!(diagnostics[0].position.char_no, 1);
+    }
+
+    /// Checks `process_token` records where each word begins within the token.
+    #[test]
+    fn process_token_word_offset() {
+        let pathbuf = PathBuf::new();
+        let read_position = ReadPosition {
+            file: pathbuf,
+            line_no: 1,
+            char_no: 1,
+            byte_no: 1,
+            utf16_no: 1,
+        };
+        let token = "leftMethodRigth".to_owned();
+        let mut words_allowed: HashSet<String> = HashSet::new();
+        words_allowed.insert("left".to_owned());
+        words_allowed.insert("method".to_owned());
+        let mut diagnostics: Vec<Diagnostic> = Vec::new();
+        let incorrect = process_token(
+            &read_position,
+            &token,
+            &words_allowed,
+            4,
+            true,
+            &mut DialectChecker::new(&DialectVariants::default(), None),
+            &mut diagnostics,
+        );
         assert!(incorrect);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].word, "Rigth");
+        assert_eq!(diagnostics[0].position.char_no, 11);
+        assert_eq!(diagnostics[0].end_char_no, 16);
+    }
+
+    /// Checks `process_token` records the column of each word in bytes and UTF-16 code units, as
+    /// well as in characters.
+    #[test]
+    fn process_token_column_modes() {
+        let read_position = ReadPosition {
+            file: PathBuf::new(),
+            line_no: 1,
+            char_no: 3,
+            byte_no: 6,
+            utf16_no: 4,
+        };
+        let words_allowed: HashSet<String> = HashSet::from(["naïve".to_owned()]);
+        let mut diagnostics: Vec<Diagnostic> = Vec::new();
+        process_token(
+            &read_position,
+            "naïveRéél",
+            &words_allowed,
+            4,
+            true,
+            &mut DialectChecker::new(&DialectVariants::default(), None),
+            &mut diagnostics,
+        );
+        assert_eq!(diagnostics.len(), 1);
+        let diagnostic = &diagnostics[0];
+        assert_eq!(diagnostic.position.column(ColumnMode::Char), 8);
+        assert_eq!(diagnostic.position.column(ColumnMode::Byte), 12);
+        assert_eq!(diagnostic.position.column(ColumnMode::Utf16), 9);
+        assert_eq!(diagnostic.end_column(ColumnMode::Char), 12);
+        assert_eq!(diagnostic.end_column(ColumnMode::Byte), 18);
+        assert_eq!(diagnostic.end_column(ColumnMode::Utf16), 13);
+    }
+
+    /// Checks `read_file` reports a word repeated across whitespace and line breaks, but not one
+    /// separated by punctuation.
+    #[test]
+    fn read_file_repeated_word() {
+        let file = std::env::temp_dir().join("antiseptic_read_file_repeated_word.txt");
+        std::fs::write(&file, "The the cat sat. Sat on\non the, the mat mat").unwrap();
+        let configuration = Configuration::default();
+        let mut diagnostics: Vec<Diagnostic> = Vec::new();
+        let result = read_file(
+            &file,
+            &Language::PlainText,
+            &configuration,
+            &SkipPatterns::new(&configuration.skip, &[]),
+            &HashSet::new(),
+            &HashSet::new(),
+            &DialectVariants::default(),
+            &mut diagnostics,
+        );
+        std::fs::remove_file(&file).unwrap();
+        assert_eq!(result, Err(AntisepticError::SpellingMistakeFound));
+        let positions: Vec<(Rule, u64, u64)> = diagnostics
+            .iter()
+            .map(|diagnostic| {
+                (
+                    diagnostic.rule,
+                    diagnostic.position.line_no,
+                    diagnostic.position.char_no,
+                )
+            })
+            .collect();
+        assert_eq!(
+            positions,
+            vec![
+                (Rule::RepeatedWord, 1, 5),
+                (Rule::RepeatedWord, 2, 1),
+                (Rule::RepeatedWord, 2, 17)
+            ]
+        );
+    }
+
+    /// Checks `read_file` only recognizes directives within comments, rather than e.g. within
+    /// string literals.
+    #[test]
+    fn read_file_directives_in_comments() {
+        let file = std::env::temp_dir().join("antiseptic_read_file_directives_in_comments.py");
+        std::fs::write(
+            &file,
+            "x = \"antiseptic: skip-file glubbage\"\ny = \"glimp\"  # noqa\nz = \"antiseptic: ignore glorp\"\n",
+        )
+        .unwrap();
+        let configuration = Configuration::default();
+        let words_allowed: HashSet<String> = ["antiseptic", "skip", "file", "ignore"]
+            .iter()
+            .map(|word| word.to_string())
+            .collect();
+        let mut diagnostics: Vec<Diagnostic> = Vec::new();
+        let result = read_file(
+            &file,
+            &Language::Python,
+            &configuration,
+            &SkipPatterns::new(&configuration.skip, &[]),
+            &HashSet::new(),
+            &words_allowed,
+            &DialectVariants::default(),
+            &mut diagnostics,
+        );
+        std::fs::remove_file(&file).unwrap();
+        assert_eq!(result, Err(AntisepticError::SpellingMistakeFound));
+        let words: Vec<(u64, &str)> = diagnostics
+            .iter()
+            .map(|diagnostic| (diagnostic.position.line_no, diagnostic.word.as_str()))
+            .collect();
+        assert_eq!(words, vec![(1, "glubbage"), (3, "glorp")]);
+    }
+
+    /// Checks `read_file` reports forbidden words, and forbidden phrases spanning multiple lines,
+    /// with any replacement matching their case.
+    #[test]
+    fn read_file_forbidden_words() {
+        let file = std::env::temp_dir().join("antiseptic_read_file_forbidden_words.txt");
+        std::fs::write(
+            &file,
+            "We utilize the Master\n  branch. Whitelist, whitelisted.",
+        )
+        .unwrap();
+        let configuration = Configuration {
+            forbidden_words: vec![
+                ForbiddenWord {
+                    words: vec!["utilize".to_owned()],
+                    replacement: Some("use".to_owned()),
+                    message: Some("Prefer plain English.".to_owned()),
+                },
+                ForbiddenWord {
+                    words: vec!["master".to_owned(), "branch".to_owned()],
+                    replacement: Some("main branch".to_owned()),
+                    message: None,
+                },
+                ForbiddenWord {
+                    words: vec!["whitelist".to_owned()],
+                    replacement: None,
+                    message: None,
+                },
+            ],
+            ..Default::default()
+        };
+        let mut diagnostics: Vec<Diagnostic> = Vec::new();
+        let _ = read_file(
+            &file,
+            &Language::PlainText,
+            &configuration,
+            &SkipPatterns::new(&configuration.skip, &[]),
+            &HashSet::new(),
+            &HashSet::new(),
+            &DialectVariants::default(),
+            &mut diagnostics,
+        );
+        std::fs::remove_file(&file).unwrap();
+        let forbidden: Vec<(u64, u64, u64, u64, Option<String>)> = diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.rule == Rule::ForbiddenWord)
+            .map(|diagnostic| {
+                (
+                    diagnostic.position.line_no,
+                    diagnostic.position.char_no,
+                    diagnostic.end_line_no,
+                    diagnostic.end_char_no,
+                    diagnostic.fix.clone(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            forbidden,
+            vec![
+                (1, 4, 1, 11, Some("use".to_owned())),
+                (1, 16, 2, 9, Some("Main branch".to_owned())),
+                (2, 11, 2, 20, None)
+            ]
+        );
+    }
+
+    /// Checks `split_token` splits snake_case, SCREAMING_SNAKE, kebab-case and camelCase tokens.
+    #[test]
+    fn split_token_separators() {
+        assert_eq!(
+            split_token("my_var_NAME-kebab", true),
+            vec![
+                (0, "my".to_owned()),
+                (3, "var".to_owned()),
+                (7, "NAME".to_owned()),
+                (12, "kebab".to_owned())
+            ]
+        );
+        assert_eq!(
+            split_token("ABCMethodRigth", true),
+            vec![
+                (0, "ABC".to_owned()),
+                (3, "Method".to_owned()),
+                (9, "Rigth".to_owned())
+            ]
+        );
+    }
+
+    /// Checks `split_token` splits at digits only when requested.
+    #[test]
+    fn split_token_digits() {
+        assert_eq!(
+            split_token("word2vec", true),
+            vec![(0, "word".to_owned()), (5, "vec".to_owned())]
+        );
+        assert_eq!(
+            split_token("ipv6Address", false),
+            vec![(0, "ipv6".to_owned()), (4, "Address".to_owned())]
+        );
     }
 }
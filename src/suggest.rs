@@ -0,0 +1,576 @@
+use std::cmp::min;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use crate::diagnostic::Diagnostic;
+use crate::fix::match_case;
+use crate::rules::Rule;
+
+/// The closest words to a misspelling, nearest first, along with its unambiguous correction, if
+/// there is one.
+type Lookup = (Vec<String>, Option<String>);
+
+/// The greatest Damerau–Levenshtein distance at which a word is suggested.
+const MAX_DISTANCE: usize = 2;
+
+/// The number of characters at the start of each word from which deletions are indexed. Edits
+/// beyond the prefix do not need indexing, since the words are compared in full afterwards.
+const PREFIX_LENGTH: usize = 7;
+
+/// The number of low bits of each key of the index holding the number of a word, leaving the
+/// remaining high bits for the hash of a deletion.
+const WORD_BITS: u32 = 24;
+
+/// An index over every correctly spelled word, allowing the closest words to a misspelling to be
+/// found without comparing the misspelling against the entire dictionary.
+///
+/// This uses symmetric deletion, as in SymSpell: two words within a distance of each other share a
+/// string reached by deleting at most that many characters from each. The index holds the hash of
+/// every string reached by deleting up to `MAX_DISTANCE` characters from the prefix of each word,
+/// so that only the words sharing one of the misspelling's deletions need to be compared with it.
+pub struct SuggestionIndex {
+    /// Every word considered correct, in lowercase and sorted.
+    words: Vec<String>,
+
+    /// The hash of each deletion in its high bits and the number of its word in its low bits,
+    /// sorted so that the words sharing a deletion are adjacent.
+    keys: Vec<u64>,
+}
+
+/// Returns the hash of every string reached by deleting up to a number of characters from the
+/// prefix of a word, with the low bits cleared to make room for the number of a word.
+///
+/// * `characters` - The characters of the word.
+/// * `max_deletions` - The greatest number of characters deleted.
+fn deletion_hashes(characters: &[char], max_deletions: usize) -> Vec<u64> {
+    let prefix = &characters[..characters.len().min(PREFIX_LENGTH)];
+
+    // Uses the 64-bit FNV-1a hash, whose high bits are kept.
+    let hash = |skipped: [usize; 2]| {
+        let hash = prefix
+            .iter()
+            .enumerate()
+            .filter(|(position, _)| !skipped.contains(position))
+            .fold(0xcbf29ce484222325, |hash: u64, (_, character)| {
+                (hash ^ *character as u64).wrapping_mul(0x100000001b3)
+            });
+        hash & !((1 << WORD_BITS) - 1)
+    };
+
+    let mut hashes = vec![hash([usize::MAX; 2])];
+    if max_deletions >= 1 {
+        for first in 0..prefix.len() {
+            hashes.push(hash([first, usize::MAX]));
+            if max_deletions >= 2 {
+                for second in first + 1..prefix.len() {
+                    hashes.push(hash([first, second]));
+                }
+            }
+        }
+    }
+    hashes.sort_unstable();
+    hashes.dedup();
+    hashes
+}
+
+/// A word preprocessed into bitmasks, one per character, marking where each character appears.
+/// This allows the Levenshtein distance to other words to be calculated a whole column at a time.
+struct Pattern {
+    /// The masks for ASCII characters, indexed by their code point.
+    ascii: [u64; 128],
+
+    /// The masks for any other characters.
+    other: Vec<(char, u64)>,
+
+    /// The characters of the word.
+    characters: Vec<char>,
+}
+
+impl Pattern {
+    /// Preprocesses a word into bitmasks.
+    ///
+    /// * `characters` - The characters of the word.
+    fn new(characters: &[char]) -> Pattern {
+        let mut pattern = Pattern {
+            ascii: [0; 128],
+            other: Vec::new(),
+            characters: characters.to_vec(),
+        };
+
+        // Words too long for the masks are handled without them.
+        if characters.len() > 64 {
+            return pattern;
+        }
+        for (position, character) in characters.iter().enumerate() {
+            let bit = 1 << position;
+            if character.is_ascii() {
+                pattern.ascii[*character as usize] |= bit;
+            } else {
+                match pattern.other.iter_mut().find(|(c, _)| c == character) {
+                    Some((_, mask)) => *mask |= bit,
+                    None => pattern.other.push((*character, bit)),
+                }
+            }
+        }
+        pattern
+    }
+
+    /// Returns the mask marking every position at which a character appears in the word.
+    ///
+    /// * `character` - The character being looked up.
+    fn mask(&self, character: char) -> u64 {
+        if character.is_ascii() {
+            return self.ascii[character as usize];
+        }
+        self.other
+            .iter()
+            .find(|(c, _)| *c == character)
+            .map_or(0, |(_, mask)| *mask)
+    }
+
+    /// Returns the Levenshtein distance between this word and another, i.e. the minimum number of
+    /// insertions, deletions and substitutions required to turn one word into the other.
+    ///
+    /// This uses Myers' bit-parallel algorithm, falling back to the standard dynamic programming
+    /// algorithm for words longer than 64 characters.
+    ///
+    /// * `text` - The characters of the other word.
+    fn levenshtein(&self, text: &[char]) -> usize {
+        let length = self.characters.len();
+        if length == 0 {
+            return text.len();
+        }
+        if length > 64 {
+            return levenshtein(&self.characters, text);
+        }
+
+        let last = 1 << (length - 1);
+        let mut positive_vertical: u64 = u64::MAX;
+        let mut negative_vertical: u64 = 0;
+        let mut score = length;
+        for character in text {
+            let equal = self.mask(*character);
+            let vertical = equal | negative_vertical;
+            let horizontal = (((equal & positive_vertical).wrapping_add(positive_vertical))
+                ^ positive_vertical)
+                | equal;
+            let mut positive_horizontal = negative_vertical | !(horizontal | positive_vertical);
+            let mut negative_horizontal = positive_vertical & horizontal;
+            if positive_horizontal & last != 0 {
+                score += 1;
+            } else if negative_horizontal & last != 0 {
+                score -= 1;
+            }
+            positive_horizontal = (positive_horizontal << 1) | 1;
+            negative_horizontal <<= 1;
+            positive_vertical = negative_horizontal | !(vertical | positive_horizontal);
+            negative_vertical = positive_horizontal & vertical;
+        }
+        score
+    }
+}
+
+/// Returns the Levenshtein distance between two words.
+///
+/// * `a` - The characters of the first word.
+/// * `b` - The characters of the second word.
+fn levenshtein(a: &[char], b: &[char]) -> usize {
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        let mut diagonal = row[0];
+        row[0] = i;
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            let next = min(min(row[j] + 1, row[j - 1] + 1), diagonal + cost);
+            diagonal = row[j];
+            row[j] = next;
+        }
+    }
+    row[b.len()]
+}
+
+/// Returns the Damerau–Levenshtein distance between two words, i.e. the minimum number of
+/// insertions, deletions, substitutions and transpositions of adjacent characters required to turn
+/// one word into the other.
+///
+/// * `a` - The characters of the first word.
+/// * `b` - The characters of the second word.
+pub fn damerau_levenshtein(a: &[char], b: &[char]) -> usize {
+    let width = b.len() + 2;
+    let infinity = a.len() + b.len();
+
+    // The distance matrix is padded with an extra row and column holding "infinite" distances.
+    let mut matrix = vec![0; (a.len() + 2) * width];
+    matrix[0] = infinity;
+    for i in 0..=a.len() {
+        matrix[(i + 1) * width] = infinity;
+        matrix[(i + 1) * width + 1] = i;
+    }
+    for j in 0..=b.len() {
+        matrix[j + 1] = infinity;
+        matrix[width + j + 1] = j;
+    }
+
+    for i in 1..=a.len() {
+        let mut last_matching_column = 0;
+        for j in 1..=b.len() {
+            // Finds the last row in which the current character of `b` was seen in `a`.
+            let previous_row = a[..i - 1]
+                .iter()
+                .rposition(|character| *character == b[j - 1])
+                .map_or(0, |position| position + 1);
+            let previous_column = last_matching_column;
+            let cost = if a[i - 1] == b[j - 1] {
+                last_matching_column = j;
+                0
+            } else {
+                1
+            };
+
+            let substitution = matrix[i * width + j] + cost;
+            let insertion = matrix[(i + 1) * width + j] + 1;
+            let deletion = matrix[i * width + j + 1] + 1;
+            let transposition = matrix[previous_row * width + previous_column]
+                + (i - previous_row - 1)
+                + 1
+                + (j - previous_column - 1);
+            matrix[(i + 1) * width + j + 1] =
+                min(min(substitution, insertion), min(deletion, transposition));
+        }
+    }
+
+    matrix[(a.len() + 1) * width + b.len() + 1]
+}
+
+/// Returns the number of characters which appear in one word more times than in the other, i.e.
+/// the size of the difference between the two words' multisets of characters. Transposing
+/// characters leaves this unchanged, while inserting or deleting a character changes it by one and
+/// substituting a character changes it by two.
+///
+/// * `a` - The characters of the first word.
+/// * `b` - The characters of the second word.
+fn bag_distance(a: &[char], b: &[char]) -> usize {
+    let mut counts: HashMap<char, isize> = HashMap::new();
+    for character in a {
+        *counts.entry(*character).or_default() += 1;
+    }
+    for character in b {
+        *counts.entry(*character).or_default() -= 1;
+    }
+    counts.values().map(|count| count.unsigned_abs()).sum()
+}
+
+impl SuggestionIndex {
+    /// Builds the index from every word considered correct.
+    ///
+    /// * `words_allowed` - The set of words which are considered correct.
+    pub fn new(words_allowed: &HashSet<String>) -> SuggestionIndex {
+        // Words are sorted so that the index is reproducible.
+        let mut words: Vec<String> = words_allowed.iter().map(|w| w.to_lowercase()).collect();
+        words.sort();
+        words.dedup();
+        debug_assert!(words.len() < 1 << WORD_BITS);
+
+        let mut keys = Vec::new();
+        for (number, word) in words.iter().enumerate() {
+            let characters: Vec<char> = word.chars().collect();
+            for hash in deletion_hashes(&characters, MAX_DISTANCE) {
+                keys.push(hash | number as u64);
+            }
+        }
+        keys.sort_unstable();
+        SuggestionIndex { words, keys }
+    }
+
+    /// Returns every word close enough to a misspelling to be suggested, nearest first.
+    ///
+    /// Each word is accompanied by its Damerau–Levenshtein distance from the misspelling, and its
+    /// bag distance from the misspelling. Words at an equal distance are ordered by the latter, and
+    /// then alphabetically.
+    ///
+    /// * `word` - The misspelled word.
+    fn candidates(&self, word: &str) -> Vec<(usize, usize, &str)> {
+        let characters: Vec<char> = word.to_lowercase().chars().collect();
+        let pattern = Pattern::new(&characters);
+
+        // Short words tolerate fewer edits, since otherwise almost any word would be suggested.
+        let max_distance = if characters.len() <= 4 {
+            1
+        } else {
+            MAX_DISTANCE
+        };
+
+        // Finds every word sharing a deletion with the misspelling.
+        let mut numbers: Vec<usize> = Vec::new();
+        for hash in deletion_hashes(&characters, max_distance) {
+            let start = self.keys.partition_point(|key| *key < hash);
+            let end = self
+                .keys
+                .partition_point(|key| *key <= hash | ((1 << WORD_BITS) - 1));
+            numbers.extend(
+                self.keys[start..end]
+                    .iter()
+                    .map(|key| (key & ((1 << WORD_BITS) - 1)) as usize),
+            );
+        }
+        numbers.sort_unstable();
+        numbers.dedup();
+
+        let mut candidates: Vec<(usize, usize, &str)> = Vec::new();
+        for number in numbers {
+            let candidate = &self.words[number];
+            let candidate_characters: Vec<char> = candidate.chars().collect();
+            if candidate_characters.len().abs_diff(characters.len()) > max_distance {
+                continue;
+            }
+
+            // A transposition is two Levenshtein edits, so the cheaper Levenshtein distance rules
+            // out most words before the Damerau–Levenshtein distance is calculated.
+            if pattern.levenshtein(&candidate_characters) > 2 * max_distance {
+                continue;
+            }
+            let distance = damerau_levenshtein(&candidate_characters, &characters);
+            if distance > max_distance {
+                continue;
+            }
+
+            // Between equally distant words, those sharing more of the misspelling's characters
+            // are preferred. Swapped characters (e.g. `helol`) are then preferred over a character
+            // typed too few or too many times (e.g. `mesage`), which are preferred over a mistyped
+            // character.
+            let bag = bag_distance(&candidate_characters, &characters);
+            candidates.push((distance, bag, candidate));
+        }
+
+        candidates.sort();
+        candidates
+    }
+
+    /// Returns the closest words to a misspelling, nearest first, along with the correction for the
+    /// misspelling, provided there is exactly one word a single edit away. Any other word at the
+    /// same distance makes the correction ambiguous.
+    ///
+    /// * `word` - The misspelled word.
+    /// * `limit` - The maximum number of suggestions to return.
+    pub fn lookup(&self, word: &str, limit: usize) -> Lookup {
+        let candidates = self.candidates(word);
+        let correction = match candidates[..] {
+            [(1, _, best), (next_distance, _, _), ..] if next_distance != 1 => {
+                Some(best.to_owned())
+            }
+            [(1, _, best)] => Some(best.to_owned()),
+            _ => None,
+        };
+        let suggestions = candidates
+            .into_iter()
+            .take(limit)
+            .map(|(_, _, word)| word.to_owned())
+            .collect();
+        (suggestions, correction)
+    }
+}
+
+/// Attaches suggested corrections to every spelling mistake, along with a fix where there is a
+/// single unambiguous correction. Corrections are only drawn from the words allowed in the file
+/// containing the mistake, so that any overrides applying to the file are respected.
+///
+/// Each index is only built once a spelling mistake needs it, since building an index is
+/// considerably slower than the spell-check itself.
+///
+/// * `diagnostics` - Every problem found during the spell-check.
+/// * `words_allowed` - The set of words which are considered correct in files without overrides
+///   changing them.
+/// * `override_word_sets` - The set of words allowed under each combination of overrides which
+///   change them.
+/// * `file_overrides` - The combination of overrides changing the words allowed in each file, for
+///   the files to which any apply.
+/// * `limit` - The maximum number of suggestions for each spelling mistake.
+/// * `find_fixes` - Whether fixes are needed, even if no suggestions are to be displayed.
+pub fn attach_suggestions(
+    diagnostics: &mut [Diagnostic],
+    words_allowed: &HashSet<String>,
+    override_word_sets: &HashMap<Vec<usize>, HashSet<String>>,
+    file_overrides: &HashMap<PathBuf, Vec<usize>>,
+    limit: usize,
+    find_fixes: bool,
+) {
+    if limit == 0 && !find_fixes {
+        return;
+    }
+
+    // Each index is kept along with the misspellings already looked up in it, since the same
+    // misspelling often recurs throughout a project.
+    let mut indices = HashMap::new();
+    for diagnostic in diagnostics
+        .iter_mut()
+        .filter(|diagnostic| diagnostic.rule == Rule::SpellingMistake)
+    {
+        let matched = file_overrides.get(&diagnostic.position.file);
+        let (index, found) = indices.entry(matched).or_insert_with(|| {
+            let word_set = matched.map_or(words_allowed, |matched| &override_word_sets[matched]);
+            let found: HashMap<String, Lookup> = HashMap::new();
+            (SuggestionIndex::new(word_set), found)
+        });
+        let (suggestions, correction) = found
+            .entry(diagnostic.word.to_lowercase())
+            .or_insert_with_key(|word| index.lookup(word, limit));
+        diagnostic.suggestions = suggestions
+            .iter()
+            .map(|suggestion| match_case(&diagnostic.word, suggestion))
+            .collect();
+        diagnostic.fix = correction
+            .as_ref()
+            .map(|correction| match_case(&diagnostic.word, correction));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use std::time::Duration;
+    use std::time::Instant;
+
+    use super::*;
+    use crate::spellcheck::get_word_set;
+
+    /// Checks the bit-parallel Levenshtein distance agrees with the standard algorithm.
+    #[test]
+    fn pattern_levenshtein_matches_standard() {
+        let words = [
+            "", "a", "helol", "hello", "kitten", "sitting", "naïve", "naive", "world",
+        ];
+        for a in words {
+            for b in words {
+                let a: Vec<char> = a.chars().collect();
+                let b: Vec<char> = b.chars().collect();
+                assert_eq!(Pattern::new(&a).levenshtein(&b), levenshtein(&a, &b));
+            }
+        }
+    }
+
+    /// Checks `damerau_levenshtein` counts a transposition as a single edit.
+    #[test]
+    fn damerau_levenshtein_transposition() {
+        let a: Vec<char> = "helol".chars().collect();
+        let b: Vec<char> = "hello".chars().collect();
+        assert_eq!(damerau_levenshtein(&a, &b), 1);
+    }
+
+    /// Checks `damerau_levenshtein` allows edits between transposed characters, unlike the optimal
+    /// string alignment distance.
+    #[test]
+    fn damerau_levenshtein_edit_after_transposition() {
+        let a: Vec<char> = "ca".chars().collect();
+        let b: Vec<char> = "abc".chars().collect();
+        assert_eq!(damerau_levenshtein(&a, &b), 2);
+    }
+
+    /// Checks `lookup` returns the closest words first, without penalizing transpositions.
+    #[test]
+    fn suggest_closest_first() {
+        let mut words_allowed: HashSet<String> = HashSet::new();
+        for word in ["hello", "hell", "help", "yellow", "colossal", "cellos"] {
+            words_allowed.insert(word.to_owned());
+        }
+        let index = SuggestionIndex::new(&words_allowed);
+        assert_eq!(index.lookup("helol", 3).0, vec!["hello", "hell", "help"]);
+        assert_eq!(index.lookup("colosal", 3).0, vec!["colossal"]);
+    }
+
+    /// Checks `lookup` finds words two transpositions away, which are more than two Levenshtein edits
+    /// away.
+    #[test]
+    fn lookup_two_transpositions() {
+        let mut words_allowed: HashSet<String> = HashSet::new();
+        for word in ["hello", "abcdefgh", "hexagon", "hollow"] {
+            words_allowed.insert(word.to_owned());
+        }
+        let index = SuggestionIndex::new(&words_allowed);
+        assert_eq!(index.lookup("ehlol", 3).0, vec!["hello"]);
+    }
+
+    /// Checks `lookup` prefers words missing a character over words with a substituted character.
+    #[test]
+    fn lookup_insertion_before_substitution() {
+        let mut words_allowed: HashSet<String> = HashSet::new();
+        for word in ["menage", "message", "metage"] {
+            words_allowed.insert(word.to_owned());
+        }
+        let index = SuggestionIndex::new(&words_allowed);
+        assert_eq!(
+            index.lookup("mesage", 3).0,
+            vec!["message", "menage", "metage"]
+        );
+    }
+
+    /// Checks `lookup` only returns a correction when no other word is equally close.
+    #[test]
+    fn lookup_correction_unambiguous() {
+        let mut words_allowed: HashSet<String> = HashSet::new();
+        for word in ["hello", "helot", "world", "wold", "colossal"] {
+            words_allowed.insert(word.to_owned());
+        }
+        let index = SuggestionIndex::new(&words_allowed);
+        assert_eq!(index.lookup("colosal", 0).1, Some("colossal".to_owned()));
+        assert_eq!(index.lookup("wrold", 0).1, None);
+        assert_eq!(index.lookup("helol", 0).1, None);
+        assert_eq!(index.lookup("wxyzab", 0).1, None);
+    }
+
+    /// Checks `lookup` finds the intended word for thousands of distinct typos against the real
+    /// dictionary, quickly enough for suggestions to stay enabled by default.
+    #[test]
+    fn lookup_real_dictionary_timing() {
+        let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("python/antiseptic");
+        let words_allowed = get_word_set(&src).unwrap();
+        let mut words: Vec<&String> = words_allowed.iter().filter(|w| w.len() >= 6).collect();
+        words.sort();
+
+        // Swaps the third and fourth characters of words spread across the dictionary.
+        let mut typos: Vec<(String, &str)> = Vec::new();
+        for word in words.iter().step_by(words.len() / 3000) {
+            let mut characters: Vec<char> = word.chars().collect();
+            characters.swap(2, 3);
+            let typo: String = characters.into_iter().collect();
+            if !words_allowed.contains(&typo) {
+                typos.push((typo, word));
+            }
+        }
+        assert!(typos.len() > 2500);
+
+        let start = Instant::now();
+        let index = SuggestionIndex::new(&words_allowed);
+        for (typo, word) in &typos {
+            let candidates = index.candidates(typo);
+            assert!(candidates.iter().any(|(_, _, candidate)| candidate == word));
+        }
+        let elapsed = start.elapsed();
+
+        // Unoptimized builds are considerably slower, so are allowed more time.
+        let limit = if cfg!(debug_assertions) { 60 } else { 5 };
+        assert!(elapsed < Duration::from_secs(limit), "{:?}", elapsed);
+    }
+
+    /// Checks `attach_suggestions` only draws corrections from the words allowed in each file.
+    #[test]
+    fn attach_suggestions_file_overrides() {
+        let diagnostic = |file| Diagnostic::at(file, 1, 1, Rule::SpellingMistake, "Colosal");
+        let mut diagnostics = vec![diagnostic("a.txt"), diagnostic("tests/b.txt")];
+        let words_allowed = HashSet::from(["colossal".to_owned()]);
+        let override_word_sets = HashMap::from([(vec![0], HashSet::from(["coloss".to_owned()]))]);
+        let file_overrides = HashMap::from([(PathBuf::from("tests/b.txt"), vec![0])]);
+        attach_suggestions(
+            &mut diagnostics,
+            &words_allowed,
+            &override_word_sets,
+            &file_overrides,
+            3,
+            true,
+        );
+        assert_eq!(diagnostics[0].suggestions, vec!["Colossal"]);
+        assert_eq!(diagnostics[0].fix, Some("Colossal".to_owned()));
+        assert_eq!(diagnostics[1].suggestions, vec!["Coloss"]);
+        assert_eq!(diagnostics[1].fix, None);
+    }
+}